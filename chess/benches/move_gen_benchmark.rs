@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use chess::{board::Board, move_generation::{attack_calculator::AttackCalculator, move_gen}};
+use chess::{board::Board, move_generation::{attack_calculator::AttackCalculator, move_gen, move_list::MoveList}};
 use criterion::{criterion_group, criterion_main, Criterion, BenchmarkId};
 
 fn benchmark_comparison(c: &mut Criterion) {
@@ -8,15 +8,27 @@ fn benchmark_comparison(c: &mut Criterion) {
     
     // Shared resources
     let mut board = Board::from_fen(core::config::CHESS_BENCHMARK_FEN);
-    let mut legal_moves = Vec::with_capacity(218);
+    let mut legal_moves: MoveList = MoveList::new();
 
     group.bench_function(BenchmarkId::new("legal_movegen_benchmark", ""), |b| {
         b.iter(|| {
-            criterion::black_box(move_gen::generate_moves(&mut legal_moves, &mut board));
+            criterion::black_box(move_gen::generate_moves(&mut legal_moves, &board));
             legal_moves.clear();
         });
     });
 
+    let mut position_moves: MoveList = MoveList::new();
+    move_gen::generate_moves(&mut position_moves, &board);
+
+    group.bench_function(BenchmarkId::new("make_undo_benchmark", ""), |b| {
+        b.iter(|| {
+            for &mov in position_moves.iter() {
+                let move_record = board.make_move(criterion::black_box(mov));
+                board.undo_move(move_record);
+            }
+        });
+    });
+
     group.bench_function(BenchmarkId::new("att_calc_benchmark", ""), |b| {
         b.iter(|| {
             criterion::black_box(AttackCalculator::new(&board));
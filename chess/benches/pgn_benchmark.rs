@@ -0,0 +1,87 @@
+use std::{fs::File, io::{BufReader, Cursor}, time::Duration};
+
+use chess::pgn::pgn_reader::PgnReader;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+/// Path to a real PGN database, the synthetic one is used if the variable isn't set
+const PGN_FILE_ENV_VAR: &str = "PGN_BENCHMARK_FILE";
+const SYNTHETIC_GAME_COUNT: usize = 2000;
+
+const SAMPLE_GAME: &str = r#"[Event "F/S Return Match"]
+[Site "Belgrade, Serbia JUG"]
+[Date "1992.11.04"]
+[Round "29"]
+[White "Fischer, Robert J."]
+[Black "Spassky, Boris V."]
+[Result "1/2-1/2"]
+
+1. e4 e5 2. Nf3 Nc6 3. Bb5 {This opening is called the Ruy Lopez.} 3... a6
+4. Ba4 Nf6 5. O-O Be7 6. Re1 b5 7. Bb3 d6 8. c3 O-O 9. h3 Nb8 10. d4 Nbd7
+11. c4 c6 12. cxb5 axb5 13. Nc3 Bb7 14. Bg5 b4 15. Nb1 h6 16. Bh4 c5 17. dxe5
+Nxe4 18. Bxe7 Qxe7 19. exd6 Qf6 20. Nbd2 Nxd6 21. Nc4 Nxc4 22. Bxc4 Nb6
+23. Ne5 Rae8 24. Bxf7+ Rxf7 25. Nxf7 Rxe1+ 26. Qxe1 Kxf7 27. Qe3 Qg5 28. Qxg5
+hxg5 29. b3 Ke6 30. a3 Kd6 31. axb4 cxb4 32. Ra5 Nd5 33. f3 Bc8 34. Kf2 Bf5
+35. Ra7 g6 36. Ra6+ Kc5 37. Ke1 Nf4 38. g3 Nxh3 39. Kd2 Kb5 40. Rd6 Kc5 41. Ra6
+Nf2 42. g4 Bd3 43. Re6 1/2-1/2
+
+"#;
+
+fn benchmark_comparison(c: &mut Criterion) {
+    let mut group = c.benchmark_group("robe_pgn");
+
+    match std::env::var(PGN_FILE_ENV_VAR) {
+        Ok(path) => {
+            let file_size = std::fs::metadata(&path).expect("Failed to read the PGN file metadata").len();
+            group.throughput(Throughput::Bytes(file_size));
+
+            group.bench_function(BenchmarkId::new("pgn_file_read_games", ""), |b| {
+                b.iter(|| {
+                    let file = File::open(&path).expect("Failed to open the PGN file");
+                    criterion::black_box(PgnReader::new(BufReader::with_capacity(1 << 20, file)).count());
+                });
+            });
+
+            group.bench_function(BenchmarkId::new("pgn_file_read_tags", ""), |b| {
+                b.iter(|| {
+                    let file = File::open(&path).expect("Failed to open the PGN file");
+                    let mut game_count = 0;
+                    PgnReader::new(BufReader::with_capacity(1 << 20, file)).read_next_game(|_| {
+                        game_count += 1;
+                        false
+                    });
+                    criterion::black_box(game_count);
+                });
+            });
+        }
+        Err(_) => {
+            let database = SAMPLE_GAME.repeat(SYNTHETIC_GAME_COUNT);
+            group.throughput(Throughput::Bytes(database.len() as u64));
+
+            group.bench_function(BenchmarkId::new("pgn_synthetic_read_games", ""), |b| {
+                b.iter(|| {
+                    criterion::black_box(PgnReader::new(Cursor::new(database.as_bytes())).count());
+                });
+            });
+
+            group.bench_function(BenchmarkId::new("pgn_synthetic_read_tags", ""), |b| {
+                b.iter(|| {
+                    let mut game_count = 0;
+                    PgnReader::new(Cursor::new(database.as_bytes())).read_next_game(|_| {
+                        game_count += 1;
+                        false
+                    });
+                    criterion::black_box(game_count);
+                });
+            });
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10).measurement_time(Duration::from_secs(20));
+    targets = benchmark_comparison
+}
+criterion_main!(benches);
@@ -0,0 +1,112 @@
+/* Attack queries for any square and either color. Unlike AttackCalculator, which only looks at the enemy
+of the side to move and prepares the move generation, these are meant for the evaluation, the tactics
+detection and the threat highlighting */
+use crate::{bitboards, board::Board, move_generation::pseudo_legal_move_gen, piece, precomputed_data};
+
+/// Returns the squares attacked by a piece of the provided type and color standing on the square
+pub fn get_piece_attacks_bb(piece_type: usize, color: usize, square: usize, occupancy: u64) -> u64 {
+    if piece_type == piece::PAWN {
+        precomputed_data::SQUARE_DATA.get_bb_for_pawn_attacks(color, square)
+    } else {
+        pseudo_legal_move_gen::get_attacks_bb(piece_type, square, occupancy)
+    }
+}
+
+/// Squares attacked by every piece type of both colors, calculated once for the whole position
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AttackMaps {
+    /// Goes like *attacks_bbs\[color]\[piece_type]*, the *piece::NONE* index contains the attacks of every piece
+    attacks_bbs: [[u64; 7]; 2],
+    /// Squares attacked by at least two pieces of the color
+    attacked_twice_bbs: [u64; 2],
+}
+
+impl AttackMaps {
+    pub fn new(board: &Board) -> Self {
+        let occupancy = board.get_all_occupied_squares();
+        let mut attacks_bbs = [[0; 7]; 2];
+        let mut attacked_twice_bbs = [0; 2];
+
+        for color in [piece::WHITE, piece::BLACK] {
+            for piece_type in [piece::PAWN, piece::KNIGHT, piece::BISHOP, piece::ROOK, piece::QUEEN, piece::KING] {
+                let mut piece_bb = board.get_piece_bitboard(color, piece_type);
+                while piece_bb != 0 {
+                    let square = bitboards::get_ls1b(piece_bb);
+                    piece_bb &= piece_bb - 1;
+
+                    let piece_attacks_bb = get_piece_attacks_bb(piece_type, color, square, occupancy);
+                    attacked_twice_bbs[color] |= attacks_bbs[color][piece::NONE] & piece_attacks_bb;
+                    attacks_bbs[color][piece::NONE] |= piece_attacks_bb;
+                    attacks_bbs[color][piece_type] |= piece_attacks_bb;
+                }
+            }
+        }
+
+        Self {
+            attacks_bbs,
+            attacked_twice_bbs,
+        }
+    }
+
+    /// Returns the squares attacked by the pieces of the provided type and color,
+    /// *piece::NONE* gives the squares attacked by any piece of the color
+    pub fn get_attacks_bb(&self, color: usize, piece_type: usize) -> u64 {
+        debug_assert!(color < 2, "Color out of bounds");
+        debug_assert!(piece_type < 7, "Piece type out of bounds");
+        unsafe { *self.attacks_bbs.get_unchecked(color).get_unchecked(piece_type) }
+    }
+
+    pub fn get_all_attacks_bb(&self, color: usize) -> u64 {
+        self.get_attacks_bb(color, piece::NONE)
+    }
+
+    /// Returns the squares attacked by at least two pieces of the provided color
+    pub fn get_attacked_twice_bb(&self, color: usize) -> u64 {
+        debug_assert!(color < 2, "Color out of bounds");
+        unsafe { *self.attacked_twice_bbs.get_unchecked(color) }
+    }
+}
+
+impl Board {
+    /// Returns the pieces of the provided color attacking the square, only the pieces inside of the occupancy
+    /// are taken into account and only the occupancy blocks the sliders
+    pub fn attackers_to(&self, square: usize, color: usize, occupancy: u64) -> u64 {
+        let queen_bb = self.get_piece_bitboard(color, piece::QUEEN);
+
+        // a pawn attacks the square if a pawn of the opposite color standing on it would attack the pawn
+        let attackers_bb = (precomputed_data::SQUARE_DATA.get_bb_for_pawn_attacks(1 - color, square) & self.get_piece_bitboard(color, piece::PAWN))
+            | (precomputed_data::SQUARE_DATA.get_bb_for_knight(square) & self.get_piece_bitboard(color, piece::KNIGHT))
+            | (precomputed_data::SQUARE_DATA.get_bb_for_king(square) & self.get_piece_bitboard(color, piece::KING))
+            | (precomputed_data::BISHOP_MAGIC_LOOKUP_TABLE.get_pseudo_legal_move_bb(square, occupancy)
+                & (self.get_piece_bitboard(color, piece::BISHOP) | queen_bb))
+            | (precomputed_data::ROOK_MAGIC_LOOKUP_TABLE.get_pseudo_legal_move_bb(square, occupancy)
+                & (self.get_piece_bitboard(color, piece::ROOK) | queen_bb));
+
+        attackers_bb & occupancy
+    }
+
+    /// Same as *attackers_to* but returns the attackers of both colors
+    pub fn all_attackers_to(&self, square: usize, occupancy: u64) -> u64 {
+        self.attackers_to(square, piece::WHITE, occupancy) | self.attackers_to(square, piece::BLACK, occupancy)
+    }
+
+    /// Returns the squares attacked by the pieces of the provided type and color,
+    /// use *AttackMaps* to get the attacks of every piece type at once
+    pub fn get_attacks_bb(&self, color: usize, piece_type: usize) -> u64 {
+        let occupancy = self.get_all_occupied_squares();
+        let mut piece_bb = self.get_piece_bitboard(color, piece_type);
+        let mut attacks_bb = 0;
+
+        while piece_bb != 0 {
+            let square = bitboards::get_ls1b(piece_bb);
+            piece_bb &= piece_bb - 1;
+            attacks_bb |= get_piece_attacks_bb(piece_type, color, square, occupancy);
+        }
+
+        attacks_bb
+    }
+
+    pub fn get_attack_maps(&self) -> AttackMaps {
+        AttackMaps::new(self)
+    }
+}
@@ -9,17 +9,16 @@ pub const EIGHTH_RANK_MASK:  u64 = 0xFF00000000000000;
 pub const NOT_FIRST_FILE_MASK: u64 = !0x8080808080808080;
 pub const NOT_EIGHTH_FILE_MASK: u64 = !0x101010101010101;
 
+/// Every light square of the board (h1, f1, ..., a2, ...)
+pub const LIGHT_SQUARES_MASK: u64 = 0xAA55AA55AA55AA55;
+pub const DARK_SQUARES_MASK: u64 = !LIGHT_SQUARES_MASK;
+
+/// The four central squares d4, e4, d5, e5, the hill of King of the Hill
+pub const CENTER_MASK: u64 = 0x0000001818000000;
+
 /// The entire board except all edges (ranks 1, 8 and files a, h)
 pub const BOARD_6X6_MASK: u64 = 0x7E7E7E7E7E7E00;
 
-// castling masks
-// contain the squares that are needed to be checked in order to make castling a legal move
-pub const WHITE_KING_SIDE_CASTLING_MASK:            u64 = 0x6;
-pub const BLACK_KING_SIDE_CASTLING_MASK:            u64 = 0x600000000000000;
-pub const WHITE_QUEEN_SIDE_CASTLING_MASK:           u64 = 0x70;
-pub const BLACK_QUEEN_SIDE_CASTLING_MASK:           u64 = 0x7000000000000000;
-pub const WHITE_QUEEN_SIDE_CASTLING_KING_PATH_MASK: u64 = 0x30;
-pub const BLACK_QUEEN_SIDE_CASTLING_KING_PATH_MASK: u64 = 0x3000000000000000;
 
 pub fn get_ls1b(bitboard: u64) -> usize {
     bitboard.trailing_zeros() as usize
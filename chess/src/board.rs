@@ -1,34 +1,15 @@
-use crate::{bitboards, board_representation, castling, r#move::{move_record::MoveRecord, Move, MoveType}, piece::{self, *}};
+use core::game::{GameState, Termination};
+
+use crate::{bitboards, board_representation, castling::{self, CastlingPath}, chess_parse_error::ChessParseError, r#move::{move_record::{MoveRecord, NullMoveRecord}, Move}, piece::{self, *}, position_violation::PositionViolation, precomputed_data::{self, square_data, zobrist_keys}, variant::Variant, move_generation::{atomic_move_gen, move_gen}};
 
 // constants
 /// Since the squares on the board go from 0 to 63 included, 64 is out of bounds making it easier to catch things like king abscense
 pub const INVALID_SQUARE: usize = 64;
 
-const QUEEN_SIDE_CASTLING_ROOK_SQUARE: [usize; 2] = [7, 63];
-const KING_SIDE_CASTLING_ROOK_SQUARE: [usize; 2] = [0, 56];
-
-fn get_king_side_castling_rook_square(color: usize) -> usize {
-    debug_assert!(color < 2, "Color is out of bounds");
-    unsafe {
-        *KING_SIDE_CASTLING_ROOK_SQUARE.get_unchecked(color)
-    }
-}
-
-fn get_queen_side_castling_rook_square(color: usize) -> usize {
-    debug_assert!(color < 2, "Color is out of bounds");
-    unsafe {
-        *QUEEN_SIDE_CASTLING_ROOK_SQUARE.get_unchecked(color)
-    }
-}
-
-const KING_START_SQUARES: [usize; 2] = [3, 59];
-
-pub fn get_king_start_square(color: usize) -> usize {
-    debug_assert!(color < 2, "Color is out of bounds");
-    unsafe {
-        *KING_START_SQUARES.get_unchecked(color)
-    }
-}
+/// Start squares of the kings and the castling rooks in the regular chess
+const DEFAULT_KING_START_SQUARES: [usize; 2] = [3, 59];
+const DEFAULT_KING_SIDE_CASTLING_ROOK_SQUARES: [usize; 2] = [0, 56];
+const DEFAULT_QUEEN_SIDE_CASTLING_ROOK_SQUARES: [usize; 2] = [7, 63];
 
 const KING_SIDE_CASTLING_SQUARES: [usize; 2] = [1, 57];
 const QUEEN_SIDE_CASTLING_SQUARES: [usize; 2] = [5, 61];
@@ -64,11 +45,32 @@ pub fn get_castled_queen_side_rook_square(color: usize) -> usize {
     }
 }
 
+fn get_back_rank_mask(color: usize) -> u64 {
+    if color == WHITE {bitboards::FIRST_RANK_MASK} else {bitboards::EIGHTH_RANK_MASK}
+}
+
+const DEFAULT_CASTLING_PATHS: [[CastlingPath; 2]; 2] = [
+    [
+        CastlingPath::new(DEFAULT_KING_START_SQUARES[WHITE], DEFAULT_KING_SIDE_CASTLING_ROOK_SQUARES[WHITE], KING_SIDE_CASTLING_SQUARES[WHITE], CASTLED_KING_SIDE_ROOK_SQUARES[WHITE]),
+        CastlingPath::new(DEFAULT_KING_START_SQUARES[WHITE], DEFAULT_QUEEN_SIDE_CASTLING_ROOK_SQUARES[WHITE], QUEEN_SIDE_CASTLING_SQUARES[WHITE], CASTLED_QUEEN_SIDE_ROOK_SQUARES[WHITE]),
+    ],
+    [
+        CastlingPath::new(DEFAULT_KING_START_SQUARES[BLACK], DEFAULT_KING_SIDE_CASTLING_ROOK_SQUARES[BLACK], KING_SIDE_CASTLING_SQUARES[BLACK], CASTLED_KING_SIDE_ROOK_SQUARES[BLACK]),
+        CastlingPath::new(DEFAULT_KING_START_SQUARES[BLACK], DEFAULT_QUEEN_SIDE_CASTLING_ROOK_SQUARES[BLACK], QUEEN_SIDE_CASTLING_SQUARES[BLACK], CASTLED_QUEEN_SIDE_ROOK_SQUARES[BLACK]),
+    ],
+];
+
 const EMPTY_SQUARES: [(usize, usize); 64] = [(piece::INVALID_COLOR, piece::NONE); 64];
 const EMPTY_PIECES: [[u64; 7]; 2] = unsafe { std::mem::zeroed() };
 const DEFAULT_CASTLING_STATES: [u8; 2] = [0b11, 0b11];
+const EMPTY_POCKETS: [[u8; 7]; 2] = [[0; 7]; 2];
+/// Piece types that can be in a Crazyhouse pocket, in the order they are written in FEN
+pub const POCKET_PIECE_TYPES: [usize; 5] = [QUEEN, ROOK, BISHOP, KNIGHT, PAWN];
+/// Initial capacity of the position history, most games are shorter than that
+const MAX_GAME_LENGTH: usize = 512;
 
 /// Contains everything about the current position
+#[derive(Clone)]
 pub struct Board {
     ///Contains bitboards for every piece type for each color
     /// 
@@ -80,82 +82,341 @@ pub struct Board {
     /// It is integer instead of bool because it is used in array indexing like in *king_square* or *piece*
     current_color: usize,
     castling_states: [u8; 2],
+    /// Goes like *castling_paths\[color]\[castling side index]*, see *castling::get_side_index*
+    castling_paths: [[CastlingPath; 2]; 2],
+    king_start_squares: [usize; 2],
+    /// In Chess960 the castling rooks can start on any file, the castling is written
+    /// in X-FEN and in UCI it is written as the king capturing its own rook
+    is_chess960: bool,
+    variant: Variant,
+    /// Goes like *pockets\[color]\[piece_type]*, the captured pieces ready to be dropped in Crazyhouse
+    pockets: [[u8; 7]; 2],
+    /// Pieces that were promoted from pawns, in Crazyhouse they go to the pocket as pawns when captured
+    promoted_pieces_bb: u64,
+    /// Pieces blown up by the Atomic captures as *(square, color, piece_type)*, the undo takes them back from the end
+    exploded_pieces: Vec<(usize, usize, usize)>,
+    /// Number of checks given by each color in Three-check
+    check_counts: [u8; 2],
     is_en_passant_possible: bool,
     en_passant_pawn_square: usize,
     en_passant_capture_square: usize,
+    /// Number of halfmoves since the last capture or pawn move, used for the fifty-move rule
+    halfmove_clock: usize,
+    /// Starts at 1 and gets incremented after every move of black
+    fullmove_number: usize,
+    /// Zobrist hash of the position, updated incrementally on every change of the board
+    zobrist_key: u64,
+    /// Zobrist keys of every position before the current one, used for the repetition detection
+    key_history: Vec<u64>,
 }
 
 impl Board {
     /// Creates empty board
     pub fn new () -> Self {
-        Self {
+        let mut board = Self {
             pieces: EMPTY_PIECES,
             squares: EMPTY_SQUARES,
             current_color: WHITE,
             castling_states: DEFAULT_CASTLING_STATES,
+            castling_paths: DEFAULT_CASTLING_PATHS,
+            king_start_squares: DEFAULT_KING_START_SQUARES,
+            is_chess960: false,
+            variant: Variant::Standard,
+            pockets: EMPTY_POCKETS,
+            promoted_pieces_bb: 0,
+            exploded_pieces: Vec::new(),
+            check_counts: [0; 2],
             is_en_passant_possible: false,
             en_passant_pawn_square: INVALID_SQUARE,
             en_passant_capture_square: INVALID_SQUARE,
-        }
+            halfmove_clock: 0,
+            fullmove_number: 1,
+            zobrist_key: 0,
+            key_history: Vec::with_capacity(MAX_GAME_LENGTH),
+        };
+
+        board.zobrist_key = board.compute_hash();
+        board
     }
 
     /// Creates board from a position in the provided FEN string
+    /// 
+    /// Panics if the FEN string is invalid, use *try_from_fen* for the user provided positions
     pub fn from_fen(fen_string: &str) -> Self {
-        let fen_data: Vec<&str> = fen_string.split(" ").collect();
+        match Self::try_from_fen(fen_string) {
+            Ok(board) => board,
+            Err(error) => panic!("Invalid FEN \"{}\": {}", fen_string, error),
+        }
+    }
+
+    /// Creates board from a position in the provided FEN string or describes what is wrong with it
+    pub fn try_from_fen(fen_string: &str) -> Result<Self, ChessParseError> {
+        let mut fen_data = fen_string.split_whitespace();
 
         let mut board = Self::new();
-        board.load_position(fen_data[0]);
+        let (piece_placement, pocket) = Self::split_pocket(fen_data.next().ok_or(ChessParseError::MissingFenField("piece placement"))?)?;
+        board.load_position(piece_placement)?;
+        if let Some(pocket) = pocket {
+            board.variant = Variant::Crazyhouse;
+            board.load_pocket(pocket)?;
+        }
 
-        if fen_data[1] == "b" {
-            board.switch_color();
+        match fen_data.next().ok_or(ChessParseError::MissingFenField("side to move"))? {
+            "w" => (),
+            "b" => board.switch_color(),
+            side_to_move => return Err(ChessParseError::InvalidSideToMove(side_to_move.to_string())),
         }
 
-        for i in 0..2 {
-            let can_short = fen_data[2].contains(if i == 0 {'K'} else {'k'});
-            let can_long = fen_data[2].contains(if i == 0 {'Q'} else {'q'});
+        let castling_field = fen_data.next().ok_or(ChessParseError::MissingFenField("castling"))?;
+        board.load_castling_states(castling_field)?;
+
+        // the en passant field contains the square behind the pawn that has just made a double move
+        if let Some(en_passant_field) = fen_data.next() {
+            if en_passant_field != "-" {
+                let capture_square = board_representation::try_get_square_from_name(en_passant_field)
+                    .map_err(|_| ChessParseError::InvalidEnPassantField(en_passant_field.to_string()))?;
+                let expected_rank = if board.is_white_to_move() {5} else {2};
+                if square_data::get_rank_from_square(capture_square) != expected_rank {
+                    return Err(ChessParseError::InvalidEnPassantField(en_passant_field.to_string()));
+                }
+                let pawn_square = if board.is_white_to_move() {capture_square - 8} else {capture_square + 8};
+                board.update_en_passant_state(true, pawn_square, capture_square);
+            }
+        }
 
-            board.castling_states[i] = 0b11;
+        // the Three-check counters are written after the move counters as *+W+B*
+        let mut remaining_fields: Vec<&str> = fen_data.collect();
+        if let Some(check_count_field) = remaining_fields.pop_if(|field| field.starts_with('+')) {
+            board.variant = Variant::ThreeCheck;
+            board.load_check_counts(check_count_field)?;
+        }
+        let mut fen_data = remaining_fields.into_iter();
+
+        // the move counters are optional since a lot of positions (like EPD ones) are stored without them
+        if let Some(halfmove_clock) = fen_data.next() {
+            board.halfmove_clock = halfmove_clock.parse()
+                .map_err(|_| ChessParseError::InvalidHalfmoveClock(halfmove_clock.to_string()))?;
+        }
+        if let Some(fullmove_number) = fen_data.next() {
+            board.fullmove_number = fullmove_number.parse()
+                .map_err(|_| ChessParseError::InvalidFullmoveNumber(fullmove_number.to_string()))?;
+        }
+
+        if let Some(unexpected_field) = fen_data.next() {
+            return Err(ChessParseError::UnexpectedFenField(unexpected_field.to_string()));
+        }
+
+        // the castling and en passant states were loaded directly so the key has to be computed from scratch
+        board.zobrist_key = board.compute_hash();
+
+        Ok(board)
+    }
+
+    /// Separates the Crazyhouse pocket from the piece placement, it is written either
+    /// after the placement in brackets (*\[QNp]*) or as its ninth rank
+    fn split_pocket(placement_field: &str) -> Result<(&str, Option<&str>), ChessParseError> {
+        if let Some((piece_placement, pocket)) = placement_field.split_once('[') {
+            let pocket = pocket.strip_suffix(']').ok_or_else(|| ChessParseError::InvalidPocket(pocket.to_string()))?;
+            return Ok((piece_placement, Some(pocket)));
+        }
 
-            if !can_short {
-                castling::annul_king_side(&mut board.castling_states[i]);
+        match placement_field.rsplit_once('/') {
+            Some((piece_placement, pocket)) if placement_field.matches('/').count() == 8 => Ok((piece_placement, Some(pocket))),
+            _ => Ok((placement_field, None)),
+        }
+    }
+
+    fn load_pocket(&mut self, pocket: &str) -> Result<(), ChessParseError> {
+        let invalid_pocket = || ChessParseError::InvalidPocket(pocket.to_string());
+        if pocket == "-" {
+            return Ok(());
+        }
+
+        for sym in pocket.chars() {
+            let &(color, piece_type) = board_representation::try_get_piece_from_fen(&sym).map_err(|_| invalid_pocket())?;
+            if !POCKET_PIECE_TYPES.contains(&piece_type) || self.get_pocket_count(color, piece_type) >= zobrist_keys::MAX_POCKET_COUNT {
+                return Err(invalid_pocket());
             }
-            if !can_long {
-                castling::annul_queen_side(&mut board.castling_states[i]);
+            self.add_to_pocket(color, piece_type);
+        }
+
+        Ok(())
+    }
+
+    /// Loads the numbers of checks given by white and black, written as *+2+1*
+    fn load_check_counts(&mut self, check_count_field: &str) -> Result<(), ChessParseError> {
+        let invalid_field = || ChessParseError::InvalidCheckCount(check_count_field.to_string());
+        let mut check_counts = check_count_field.strip_prefix('+').ok_or_else(invalid_field)?.split('+');
+
+        for color in [WHITE, BLACK] {
+            let check_count: u8 = check_counts.next()
+                .and_then(|check_count| check_count.parse().ok())
+                .ok_or_else(invalid_field)?;
+            if check_count as usize > zobrist_keys::MAX_CHECK_COUNT {
+                return Err(invalid_field());
             }
+            self.check_counts[color] = check_count;
         }
 
-        board
+        if check_counts.next().is_some() {
+            return Err(invalid_field());
+        }
+
+        Ok(())
     }
 
-    fn load_position(&mut self, fen_pos: &str) {
-        let rows: Vec<&str> = fen_pos.split("/").collect();
+    fn load_position(&mut self, fen_pos: &str) -> Result<(), ChessParseError> {
+        let rows: Vec<&str> = fen_pos.split('/').collect();
+        if rows.len() != 8 {
+            return Err(ChessParseError::WrongRankCount(rows.len()));
+        }
 
         for y in 0..8usize {
             let mut x = 7i8;
+            // a *~* after a piece marks it as promoted in Crazyhouse
+            let mut last_piece_square = None;
 
             for sym in rows[7 - y].chars() {
-                if x < 0 {
+                if sym == '~' {
+                    let square: usize = last_piece_square.take().ok_or(ChessParseError::UnknownPieceChar(sym))?;
+                    self.promoted_pieces_bb |= bitboards::get_bit_from_square(square);
                     continue;
                 }
+                last_piece_square = None;
+
+                if x < 0 {
+                    return Err(ChessParseError::WrongRankLength { rank: y + 1, length: Self::get_rank_length(rows[7 - y]) });
+                }
 
-                if sym.is_digit(10) {
-                    x -= sym.to_digit(10).expect("FEN loading") as i8;
+                if let Some(empty_squares) = sym.to_digit(10) {
+                    if empty_squares == 0 {
+                        return Err(ChessParseError::UnknownPieceChar(sym));
+                    }
+                    x -= empty_squares as i8;
                     continue;
                 }
 
-                let (color, piece_type) = board_representation::get_piece_from_fen(&sym);
+                let (color, piece_type) = board_representation::try_get_piece_from_fen(&sym)?;
+                if *piece_type == piece::NONE {
+                    return Err(ChessParseError::UnknownPieceChar(sym));
+                }
                 let square = x as usize + y * 8;
-                if *piece_type != piece::NONE {
-                    self.create_piece(square, *color, *piece_type);
+                self.create_piece(square, *color, *piece_type);
+                last_piece_square = Some(square);
+                x -= 1;
+            }
 
+            if x != -1 {
+                return Err(ChessParseError::WrongRankLength { rank: y + 1, length: Self::get_rank_length(rows[7 - y]) });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the number of squares the provided rank of the FEN piece placement describes
+    fn get_rank_length(fen_rank: &str) -> usize {
+        fen_rank.chars()
+            .filter(|&sym| sym != '~')
+            .map(|sym| sym.to_digit(10).unwrap_or(1) as usize)
+            .sum()
+    }
+
+    /// Loads both the regular *KQkq* castling field and the Shredder-FEN/X-FEN one,
+    /// which names the files of the castling rooks (*HAha*, *KGkq*)
+    fn load_castling_states(&mut self, castling_field: &str) -> Result<(), ChessParseError> {
+        let invalid_field = || ChessParseError::InvalidCastlingField(castling_field.to_string());
+
+        self.castling_states = [0, 0];
+        self.is_chess960 = false;
+        for color in [WHITE, BLACK] {
+            let king_bb = self.get_piece_bitboard(color, KING);
+            self.king_start_squares[color] = if king_bb.count_ones() == 1 && king_bb & get_back_rank_mask(color) != 0 {
+                bitboards::get_ls1b(king_bb)
+            } else {
+                DEFAULT_KING_START_SQUARES[color]
+            };
+            self.set_castling_rook_square(color, castling::KING_SIDE, DEFAULT_KING_SIDE_CASTLING_ROOK_SQUARES[color]);
+            self.set_castling_rook_square(color, castling::QUEEN_SIDE, DEFAULT_QUEEN_SIDE_CASTLING_ROOK_SQUARES[color]);
+        }
+
+        if castling_field == "-" {
+            return Ok(());
+        }
+
+        for sym in castling_field.chars() {
+            let color = if sym.is_ascii_uppercase() {WHITE} else {BLACK};
+            let king_square = self.get_king_start_square(color);
+
+            let (castling_side, rook_square) = match sym.to_ascii_lowercase() {
+                'k' => (castling::KING_SIDE, self.find_outermost_castling_rook(color, castling::KING_SIDE)
+                    .unwrap_or(DEFAULT_KING_SIDE_CASTLING_ROOK_SQUARES[color])),
+                'q' => (castling::QUEEN_SIDE, self.find_outermost_castling_rook(color, castling::QUEEN_SIDE)
+                    .unwrap_or(DEFAULT_QUEEN_SIDE_CASTLING_ROOK_SQUARES[color])),
+                file_sym @ 'a'..='h' => {
+                    // the squares go from the h-file to the a-file
+                    let rook_square = (king_square & !7) + 7 - (file_sym as usize - 'a' as usize);
+                    self.is_chess960 = true;
+                    match rook_square.cmp(&king_square) {
+                        std::cmp::Ordering::Less => (castling::KING_SIDE, rook_square),
+                        std::cmp::Ordering::Greater => (castling::QUEEN_SIDE, rook_square),
+                        std::cmp::Ordering::Equal => return Err(invalid_field()),
+                    }
                 }
-                x -= 1;
+                _ => return Err(invalid_field()),
+            };
+
+            if self.castling_states[color] & castling_side != 0 {
+                return Err(invalid_field()); // the same right can't be mentioned twice
+            }
+            self.castling_states[color] |= castling_side;
+            self.set_castling_rook_square(color, castling_side, rook_square);
+
+            let default_rook_square = if castling_side == castling::KING_SIDE {
+                DEFAULT_KING_SIDE_CASTLING_ROOK_SQUARES[color]
+            } else {
+                DEFAULT_QUEEN_SIDE_CASTLING_ROOK_SQUARES[color]
+            };
+            if king_square != DEFAULT_KING_START_SQUARES[color] || rook_square != default_rook_square {
+                self.is_chess960 = true;
             }
         }
+
+        Ok(())
+    }
+
+    fn set_castling_rook_square(&mut self, color: usize, castling_side: u8, rook_square: usize) {
+        let (king_target_square, rook_target_square) = if castling_side == castling::KING_SIDE {
+            (get_king_side_square(color), get_castled_king_side_rook_square(color))
+        } else {
+            (get_queen_side_square(color), get_castled_queen_side_rook_square(color))
+        };
+
+        self.castling_paths[color][castling::get_side_index(castling_side)] = CastlingPath::new(
+            self.get_king_start_square(color), 
+            rook_square, 
+            king_target_square, 
+            rook_target_square
+        );
+    }
+
+    /// Returns the rook on the back rank which is the furthest from the king on the provided side
+    fn find_outermost_castling_rook(&self, color: usize, castling_side: u8) -> Option<usize> {
+        let king_bit = bitboards::get_bit_from_square(self.get_king_start_square(color));
+        let back_rank_rooks_bb = self.get_piece_bitboard(color, ROOK) & get_back_rank_mask(color);
+
+        // the king side is towards the h-file, where the square indexes are lower
+        if castling_side == castling::KING_SIDE {
+            let rooks_bb = back_rank_rooks_bb & (king_bit - 1);
+            (rooks_bb != 0).then(|| bitboards::get_ls1b(rooks_bb))
+        } else {
+            let rooks_bb = back_rank_rooks_bb & !(king_bit | (king_bit - 1));
+            (rooks_bb != 0).then(|| 63 - rooks_bb.leading_zeros() as usize)
+        }
     }
 
     /// Returns the FEN string of the current position
-    pub fn to_fen(&mut self) -> String {
+    pub fn to_fen(&self) -> String {
         let mut fen_string = String::new();
 
         for y in (0..8).rev() {
@@ -174,6 +435,9 @@ impl Board {
                     }
 
                     fen_string.push(board_representation::piece_to_fen_sym(color, piece_type));
+                    if self.promoted_pieces_bb & bitboards::get_bit_from_square(square) != 0 {
+                        fen_string.push('~');
+                    }
                 }
             }
 
@@ -186,23 +450,41 @@ impl Board {
             }
         }
 
+        if self.variant.has_pockets() {
+            fen_string.push('[');
+            for color in [WHITE, BLACK] {
+                for piece_type in POCKET_PIECE_TYPES {
+                    for _ in 0..self.get_pocket_count(color, piece_type) {
+                        fen_string.push(board_representation::piece_to_fen_sym(color, piece_type));
+                    }
+                }
+            }
+            fen_string.push(']');
+        }
+
         fen_string.push(' ');
 
         fen_string.push(if self.is_white_to_move() {'w'} else {'b'});
         fen_string.push(' ');
 
         let mut castling_string = String::new();
-        if castling::can_king_side(self.castling_states[WHITE]) {
-            castling_string.push('K');
-        }
-        if castling::can_queen_side(self.castling_states[WHITE]) {
-            castling_string.push('Q');
-        }
-        if castling::can_king_side(self.castling_states[BLACK]) {
-            castling_string.push('k');
-        }
-        if castling::can_queen_side(self.castling_states[BLACK]) {
-            castling_string.push('q');
+        for color in [WHITE, BLACK] {
+            for castling_side in [castling::KING_SIDE, castling::QUEEN_SIDE] {
+                if self.castling_states[color] & castling_side == 0 {
+                    continue;
+                }
+
+                // X-FEN names the file of the rook only if it isn't the outermost one
+                let rook_square = self.get_castling_path(color, castling_side).rook_square;
+                let castling_sym = if self.is_chess960 && self.find_outermost_castling_rook(color, castling_side) != Some(rook_square) {
+                    board_representation::get_board_row_letter(7 - square_data::get_file_from_square(rook_square))
+                } else if castling_side == castling::KING_SIDE {
+                    'k'
+                } else {
+                    'q'
+                };
+                castling_string.push(if color == WHITE {castling_sym.to_ascii_uppercase()} else {castling_sym});
+            }
         }
         if castling_string.is_empty() {
             castling_string.push('-');
@@ -213,14 +495,23 @@ impl Board {
 
         if self.is_en_passant_possible() {
             fen_string.push_str(
-                &board_representation::get_square_name(
-                    self.en_passant_pawn_square()
+                board_representation::get_square_name(
+                    self.en_passant_capture_square()
                 )
             );
         } else {
             fen_string.push('-');
         }
 
+        fen_string.push(' ');
+        fen_string.push_str(&self.halfmove_clock.to_string());
+        fen_string.push(' ');
+        fen_string.push_str(&self.fullmove_number.to_string());
+
+        if self.variant == Variant::ThreeCheck {
+            fen_string.push_str(&format!(" +{}+{}", self.check_counts[WHITE], self.check_counts[BLACK]));
+        }
+
         fen_string
     }
 
@@ -250,6 +541,11 @@ impl Board {
         }
     }
 
+    /// Returns bitboard with the pieces of the provided type of both colors
+    pub fn get_piece_type_bitboard(&self, piece_type: usize) -> u64 {
+        self.get_piece_bitboard(WHITE, piece_type) | self.get_piece_bitboard(BLACK, piece_type)
+    }
+
     /// Returns piece standing on the provided square (or *INVALID_PIECE* if the square is empty)
     pub fn get_piece_on_square(&self, square: usize) -> (usize, usize) {
         debug_assert!(square < 64, "Square is out of bounds");
@@ -276,13 +572,123 @@ impl Board {
     }
 
     ///Returns the castling state for the side of the color provided
-    pub fn get_castling_state(&mut self, color: usize) -> &mut u8 {
+    pub fn get_castling_state(&self, color: usize) -> u8 {
+        debug_assert!(color < 2, "Color is out of bounds");
+        unsafe {
+            *self.castling_states.get_unchecked(color)
+        }
+    }
+
+    ///Returns the mutable castling state for the side of the color provided
+    pub fn get_castling_state_mut(&mut self, color: usize) -> &mut u8 {
         debug_assert!(color < 2, "Color is out of bounds");
         unsafe {
             &mut *self.castling_states.get_unchecked_mut(color)
         }
     }
 
+    /// Returns the squares involved in the castling of the provided side (*castling::KING_SIDE* or *castling::QUEEN_SIDE*)
+    pub fn get_castling_path(&self, color: usize, castling_side: u8) -> &CastlingPath {
+        debug_assert!(color < 2, "Color is out of bounds");
+        unsafe {
+            self.castling_paths.get_unchecked(color).get_unchecked(castling::get_side_index(castling_side))
+        }
+    }
+
+    pub fn get_king_start_square(&self, color: usize) -> usize {
+        debug_assert!(color < 2, "Color is out of bounds");
+        unsafe {
+            *self.king_start_squares.get_unchecked(color)
+        }
+    }
+
+    pub fn is_chess960(&self) -> bool {
+        self.is_chess960
+    }
+
+    /// Positions with the regular castling squares can't be told apart from Chess960 ones by their FEN,
+    /// so the mode has to be set explicitly for them
+    pub fn set_chess960(&mut self, is_chess960: bool) {
+        self.is_chess960 = is_chess960;
+    }
+
+    pub fn get_variant(&self) -> Variant {
+        self.variant
+    }
+
+    /// The variant of a FEN without a pocket can't be known, so it has to be set explicitly
+    ///
+    /// Switching to a variant without pockets empties them, and the check counters are only kept in Three-check
+    pub fn set_variant(&mut self, variant: Variant) {
+        self.variant = variant;
+        if !variant.has_castling() {
+            self.castling_states = [0; 2];
+        }
+        if !variant.has_pockets() {
+            self.pockets = EMPTY_POCKETS;
+            self.promoted_pieces_bb = 0;
+        }
+        if variant != Variant::ThreeCheck {
+            self.check_counts = [0; 2];
+        }
+        self.zobrist_key = self.compute_hash();
+    }
+
+    /// Returns how many pieces of the provided type the side can drop
+    pub fn get_pocket_count(&self, color: usize, piece_type: usize) -> usize {
+        debug_assert!(color < 2, "Color is out of bounds");
+        debug_assert!(piece_type < 7, "Piece type is out of bounds");
+        unsafe {
+            *self.pockets.get_unchecked(color).get_unchecked(piece_type) as usize
+        }
+    }
+
+    /// Returns bitboard of the pieces promoted from pawns, it is only kept in Crazyhouse
+    pub fn get_promoted_pieces_bb(&self) -> u64 {
+        self.promoted_pieces_bb
+    }
+
+    fn add_to_pocket(&mut self, color: usize, piece_type: usize) {
+        debug_assert!(color < 2, "Color is out of bounds");
+        debug_assert!(piece_type < 7, "Piece type is out of bounds");
+        unsafe {
+            let count = self.pockets.get_unchecked_mut(color).get_unchecked_mut(piece_type);
+            *count += 1;
+            self.zobrist_key ^= precomputed_data::ZOBRIST_KEYS.get_pocket_key(color, piece_type, *count as usize);
+        }
+    }
+
+    fn remove_from_pocket(&mut self, color: usize, piece_type: usize) {
+        debug_assert!(color < 2, "Color is out of bounds");
+        debug_assert!(piece_type < 7, "Piece type is out of bounds");
+        debug_assert!(self.get_pocket_count(color, piece_type) > 0, "There is no such piece in the pocket");
+        unsafe {
+            let count = self.pockets.get_unchecked_mut(color).get_unchecked_mut(piece_type);
+            self.zobrist_key ^= precomputed_data::ZOBRIST_KEYS.get_pocket_key(color, piece_type, *count as usize);
+            *count -= 1;
+        }
+    }
+
+    /// Returns how many checks the provided color has given in Three-check
+    pub fn get_check_count(&self, color: usize) -> usize {
+        debug_assert!(color < 2, "Color is out of bounds");
+        unsafe {
+            *self.check_counts.get_unchecked(color) as usize
+        }
+    }
+
+    fn add_check(&mut self, color: usize) {
+        debug_assert!(color < 2, "Color is out of bounds");
+        debug_assert!(self.get_check_count(color) < zobrist_keys::MAX_CHECK_COUNT, "Too many checks were given");
+        let zobrist_keys = &precomputed_data::ZOBRIST_KEYS;
+        let check_count = self.get_check_count(color);
+        if check_count > 0 {
+            self.zobrist_key ^= zobrist_keys.get_check_key(color, check_count);
+        }
+        self.zobrist_key ^= zobrist_keys.get_check_key(color, check_count + 1);
+        self.check_counts[color] += 1;
+    }
+
     pub fn is_en_passant_possible(&self) -> bool {
         self.is_en_passant_possible
     }
@@ -295,6 +701,346 @@ impl Board {
         self.en_passant_capture_square
     }
 
+    pub fn get_halfmove_clock(&self) -> usize {
+        self.halfmove_clock
+    }
+
+    pub fn get_fullmove_number(&self) -> usize {
+        self.fullmove_number
+    }
+
+    /// Returns the Zobrist hash of the current position
+    pub fn get_zobrist_key(&self) -> u64 {
+        self.zobrist_key
+    }
+
+    /// Calculates the Zobrist hash of the current position from scratch
+    /// 
+    /// The result must always be equal to the incrementally updated *get_zobrist_key*
+    pub fn compute_hash(&self) -> u64 {
+        let zobrist_keys = &precomputed_data::ZOBRIST_KEYS;
+        let mut hash = 0;
+
+        for square in 0..64 {
+            let (color, piece_type) = self.get_piece_on_square(square);
+            if piece_type != piece::NONE {
+                hash ^= zobrist_keys.get_piece_square_key(color, piece_type, square);
+            }
+        }
+
+        if !self.is_white_to_move() {
+            hash ^= zobrist_keys.get_side_to_move_key();
+        }
+
+        for color in [WHITE, BLACK] {
+            for piece_type in POCKET_PIECE_TYPES {
+                for count in 1..=self.get_pocket_count(color, piece_type) {
+                    hash ^= zobrist_keys.get_pocket_key(color, piece_type, count);
+                }
+            }
+
+            let check_count = self.get_check_count(color);
+            if check_count > 0 {
+                hash ^= zobrist_keys.get_check_key(color, check_count);
+            }
+        }
+
+        hash ^ self.get_castling_hash() ^ self.get_en_passant_hash()
+    }
+
+    /// Returns the same position seen from the other side: the ranks are mirrored and the colors are swapped
+    /// together with the side to move, the castling rights, the pockets and the check counts
+    ///
+    /// The position history is not kept, so the repetitions are not detected in the flipped position
+    pub fn get_color_flipped(&self) -> Self {
+        let mut flipped_board = Self::new();
+        flipped_board.variant = self.variant;
+        flipped_board.is_chess960 = self.is_chess960;
+        flipped_board.current_color = 1 - self.current_color;
+
+        // the rank is the upper three bits of the square
+        for square in 0..64 {
+            let (color, piece_type) = self.get_piece_on_square(square);
+            if piece_type != piece::NONE {
+                flipped_board.create_piece(square ^ 56, 1 - color, piece_type);
+            }
+        }
+        flipped_board.promoted_pieces_bb = self.promoted_pieces_bb.swap_bytes();
+
+        for color in [WHITE, BLACK] {
+            flipped_board.pockets[1 - color] = self.pockets[color];
+            flipped_board.check_counts[1 - color] = self.check_counts[color];
+            flipped_board.castling_states[1 - color] = self.castling_states[color];
+            flipped_board.king_start_squares[1 - color] = self.get_king_start_square(color) ^ 56;
+        }
+        for color in [WHITE, BLACK] {
+            for castling_side in [castling::KING_SIDE, castling::QUEEN_SIDE] {
+                let rook_square = self.get_castling_path(color, castling_side).rook_square ^ 56;
+                flipped_board.set_castling_rook_square(1 - color, castling_side, rook_square);
+            }
+        }
+
+        if self.is_en_passant_possible {
+            flipped_board.update_en_passant_state(true, self.en_passant_pawn_square ^ 56, self.en_passant_capture_square ^ 56);
+        }
+        flipped_board.halfmove_clock = self.halfmove_clock;
+        flipped_board.fullmove_number = self.fullmove_number;
+
+        flipped_board.zobrist_key = flipped_board.compute_hash();
+        flipped_board
+    }
+
+    /// Returns how many times the current position has occurred in the game, including the current occurrence
+    pub fn get_repetition_count(&self) -> usize {
+        // the positions before the last capture or pawn move can't be repeated
+        let reversible_plies = self.halfmove_clock.min(self.key_history.len());
+
+        1 + self.key_history.iter()
+            .rev()
+            .take(reversible_plies)
+            .filter(|&&key| key == self.zobrist_key)
+            .count()
+    }
+
+    pub fn is_threefold_repetition(&self) -> bool {
+        self.get_repetition_count() >= 3
+    }
+
+    pub fn is_fivefold_repetition(&self) -> bool {
+        self.get_repetition_count() >= 5
+    }
+
+    pub fn is_fifty_move_rule(&self) -> bool {
+        self.halfmove_clock >= 100
+    }
+
+    pub fn is_seventy_five_move_rule(&self) -> bool {
+        self.halfmove_clock >= 150
+    }
+
+    /// Returns true if neither side can checkmate the other: K vs K, KB vs K, KN vs K
+    /// or any number of bishops when all of them stand on the same colored squares
+    ///
+    /// In Three-check only the bare kings are dead since any other piece can give the checks
+    pub fn has_insufficient_material(&self) -> bool {
+        // captured pieces come back to the board in Crazyhouse, a lone king can still reach the hill
+        // and the Antichess games are won by losing the pieces
+        if self.variant.has_pockets() || matches!(self.variant, Variant::KingOfTheHill | Variant::Antichess) {
+            return false;
+        }
+        if self.variant == Variant::ThreeCheck {
+            return self.get_all_occupied_squares().count_ones() == 2;
+        }
+
+        let pawns_rooks_queens_bb = 
+            self.get_piece_bitboard(WHITE, PAWN) | self.get_piece_bitboard(BLACK, PAWN)
+            | self.get_piece_bitboard(WHITE, ROOK) | self.get_piece_bitboard(BLACK, ROOK)
+            | self.get_piece_bitboard(WHITE, QUEEN) | self.get_piece_bitboard(BLACK, QUEEN);
+        if pawns_rooks_queens_bb != 0 {
+            return false;
+        }
+
+        let knights_bb = self.get_piece_bitboard(WHITE, KNIGHT) | self.get_piece_bitboard(BLACK, KNIGHT);
+        let bishops_bb = self.get_piece_bitboard(WHITE, BISHOP) | self.get_piece_bitboard(BLACK, BISHOP);
+        if (knights_bb | bishops_bb).count_ones() <= 1 {
+            return true;
+        }
+
+        knights_bb == 0 
+            && (bishops_bb & bitboards::LIGHT_SQUARES_MASK == 0 || bishops_bb & bitboards::DARK_SQUARES_MASK == 0)
+    }
+
+    /// Returns the rule the game is drawn by automatically in the current position, if there is any
+    /// 
+    /// Stalemate is not included since it requires the move generation, the draws a player
+    /// has to claim are returned by *get_claimable_draw_reason*
+    pub fn get_draw_reason(&self) -> Option<Termination> {
+        if self.has_insufficient_material() {
+            Some(Termination::InsufficientMaterial)
+        } else if self.is_fivefold_repetition() {
+            Some(Termination::FivefoldRepetition)
+        } else if self.is_seventy_five_move_rule() {
+            Some(Termination::SeventyFiveMoveRule)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the rule a player can claim the draw by in the current position, the game goes on
+    /// unless the draw is claimed
+    pub fn get_claimable_draw_reason(&self) -> Option<Termination> {
+        if self.is_threefold_repetition() {
+            Some(Termination::ThreefoldRepetition)
+        } else if self.is_fifty_move_rule() {
+            Some(Termination::FiftyMoveRule)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the result of the game if it was decided by a rule of the variant, like exploding the king in Atomic
+    ///
+    /// Checkmate and stalemate are not included since they require the move generation
+    pub fn get_variant_game_state(&self) -> Option<GameState> {
+        let termination = match self.variant {
+            Variant::Atomic => Termination::KingExploded,
+            Variant::ThreeCheck => Termination::ThirdCheck,
+            Variant::KingOfTheHill => Termination::KingOfTheHill,
+            Variant::Antichess => Termination::AllPiecesLost,
+            Variant::Standard | Variant::Crazyhouse => return None,
+        };
+
+        let winner = [WHITE, BLACK].into_iter().find(|&color| match self.variant {
+            Variant::Atomic => self.get_piece_bitboard(1 - color, KING) == 0,
+            Variant::ThreeCheck => self.get_check_count(color) == zobrist_keys::MAX_CHECK_COUNT,
+            Variant::KingOfTheHill => self.get_piece_bitboard(color, KING) & bitboards::CENTER_MASK != 0,
+            _ => self.get_all_occupied_squares_for_color(color) == 0,
+        })?;
+
+        Some(if winner == WHITE {GameState::WhiteWon(termination)} else {GameState::BlackWon(termination)})
+    }
+
+    fn get_castling_hash(&self) -> u64 {
+        precomputed_data::ZOBRIST_KEYS.get_castling_key(WHITE, self.castling_states[WHITE])
+            ^ precomputed_data::ZOBRIST_KEYS.get_castling_key(BLACK, self.castling_states[BLACK])
+    }
+
+    /// The en passant file is hashed only if a pawn of the side to move stands next to 
+    /// the pawn that has just made a double move, otherwise the positions are the same
+    fn get_en_passant_hash(&self) -> u64 {
+        if !self.is_en_passant_possible() {
+            return 0;
+        }
+
+        let capture_square = self.en_passant_capture_square();
+        let capturing_pawns_bb = precomputed_data::SQUARE_DATA.get_bb_for_pawn_attacks(self.get_opposite_color(), capture_square)
+            & self.get_piece_bitboard(self.get_current_color(), PAWN);
+        if capturing_pawns_bb == 0 {
+            return 0;
+        }
+
+        precomputed_data::ZOBRIST_KEYS.get_en_passant_file_key(square_data::get_file_from_square(capture_square))
+    }
+
+    /// Returns true if any piece of the provided color attacks the provided square
+    pub fn is_square_attacked(&self, square: usize, attacker_color: usize) -> bool {
+        debug_assert!(square < 64, "Square is out of bounds");
+        debug_assert!(attacker_color < 2, "Color is out of bounds");
+        let all_occ = self.get_all_occupied_squares();
+        let queen_bb = self.get_piece_bitboard(attacker_color, QUEEN);
+
+        // a pawn attacks the square if a pawn of the opposite color standing on it would attack the pawn
+        precomputed_data::SQUARE_DATA.get_bb_for_pawn_attacks(1 - attacker_color, square) 
+            & self.get_piece_bitboard(attacker_color, PAWN) != 0
+        || precomputed_data::SQUARE_DATA.get_bb_for_knight(square) 
+            & self.get_piece_bitboard(attacker_color, KNIGHT) != 0
+        || precomputed_data::SQUARE_DATA.get_bb_for_king(square) 
+            & self.get_piece_bitboard(attacker_color, KING) != 0
+        || precomputed_data::ROOK_MAGIC_LOOKUP_TABLE.get_pseudo_legal_move_bb(square, all_occ) 
+            & (self.get_piece_bitboard(attacker_color, ROOK) | queen_bb) != 0
+        || precomputed_data::BISHOP_MAGIC_LOOKUP_TABLE.get_pseudo_legal_move_bb(square, all_occ) 
+            & (self.get_piece_bitboard(attacker_color, BISHOP) | queen_bb) != 0
+    }
+
+    /// Returns true if the king of the provided color is in check, following the rules of the variant
+    fn is_king_attacked(&self, color: usize) -> bool {
+        if self.variant == Variant::Atomic {
+            atomic_move_gen::is_king_attacked(self, color)
+        } else {
+            self.is_square_attacked(self.get_king_square(color), 1 - color)
+        }
+    }
+
+    /// Checks whether the position can occur in a legal game and returns every violation found
+    /// 
+    /// The move generation relies on the position being legal, so every position 
+    /// coming from the user should be validated before generating moves
+    pub fn validate(&self) -> Result<(), Vec<PositionViolation>> {
+        let mut violations = Vec::new();
+
+        for color in [WHITE, BLACK] {
+            let king_count = self.get_piece_bitboard(color, KING).count_ones();
+            if king_count != 1 && self.variant.is_king_royal() {
+                violations.push(PositionViolation::WrongKingCount { color, count: king_count });
+            }
+
+            // in Crazyhouse the captured pieces change sides, so they can't be counted per color
+            if !self.variant.has_pockets() {
+                let piece_count = self.get_all_occupied_squares_for_color(color).count_ones();
+                if piece_count > 16 {
+                    violations.push(PositionViolation::TooManyPieces { color, count: piece_count });
+                }
+
+                let pawn_count = self.get_piece_bitboard(color, PAWN).count_ones();
+                if pawn_count > 8 {
+                    violations.push(PositionViolation::TooManyPawns { color, count: pawn_count });
+                }
+
+                let promoted_count = self.get_piece_bitboard(color, QUEEN).count_ones().saturating_sub(1)
+                    + self.get_piece_bitboard(color, ROOK).count_ones().saturating_sub(2)
+                    + self.get_piece_bitboard(color, BISHOP).count_ones().saturating_sub(2)
+                    + self.get_piece_bitboard(color, KNIGHT).count_ones().saturating_sub(2);
+                if promoted_count > 8u32.saturating_sub(pawn_count) {
+                    violations.push(PositionViolation::TooManyPromotedPieces { color, count: promoted_count });
+                }
+            }
+
+            let castling_state = self.castling_states[color];
+            let is_king_on_start_square = self.get_piece_on_square(self.get_king_start_square(color)) == (color, KING);
+            for castling_side in [castling::KING_SIDE, castling::QUEEN_SIDE] {
+                let rook_square = self.get_castling_path(color, castling_side).rook_square;
+                if castling_state & castling_side != 0
+                && !(is_king_on_start_square && self.get_piece_on_square(rook_square) == (color, ROOK)) {
+                    violations.push(PositionViolation::InvalidCastlingRights { color, castling_side });
+                }
+            }
+        }
+
+        let mut back_rank_pawns_bb = (self.get_piece_bitboard(WHITE, PAWN) | self.get_piece_bitboard(BLACK, PAWN))
+            & (bitboards::FIRST_RANK_MASK | bitboards::EIGHTH_RANK_MASK);
+        while back_rank_pawns_bb != 0 {
+            let square = bitboards::get_ls1b(back_rank_pawns_bb);
+            back_rank_pawns_bb &= back_rank_pawns_bb - 1;
+            violations.push(PositionViolation::PawnOnBackRank { square });
+        }
+
+        let opposite_color = self.get_opposite_color();
+        // the king square is meaningless if the number of kings is wrong, which is already reported
+        if self.variant.is_king_royal()
+        && self.get_piece_bitboard(opposite_color, KING).count_ones() == 1
+        && self.is_king_attacked(opposite_color) {
+            violations.push(PositionViolation::SideNotToMoveInCheck);
+        }
+
+        if self.is_en_passant_possible() && !self.is_en_passant_state_valid() {
+            violations.push(PositionViolation::InvalidEnPassantSquare { square: self.en_passant_capture_square() });
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    /// Returns true if the opposite side could have just made a double pawn move
+    /// resulting in the current en passant state
+    fn is_en_passant_state_valid(&self) -> bool {
+        let capture_square = self.en_passant_capture_square();
+        let pawn_square = self.en_passant_pawn_square();
+        let expected_rank = if self.is_white_to_move() {5} else {2};
+        if capture_square >= 64 || square_data::get_rank_from_square(capture_square) != expected_rank {
+            return false;
+        }
+
+        // the square the pawn has made the double move from
+        let origin_square = capture_square * 2 - pawn_square;
+
+        self.get_piece_on_square(pawn_square) == (self.get_opposite_color(), PAWN)
+            && self.get_piece_on_square(capture_square).1 == piece::NONE
+            && self.get_piece_on_square(origin_square).1 == piece::NONE
+    }
+
     /// Updates the en passant state based on the provided square
     fn update_en_passant_state(&mut self, possible: bool, pawn_square: usize, capture_square: usize) {
         self.is_en_passant_possible = possible;
@@ -309,15 +1055,28 @@ impl Board {
     /// Used after every move
     pub fn switch_color(&mut self) {
         self.current_color = 1 - self.current_color;
+        self.zobrist_key ^= precomputed_data::ZOBRIST_KEYS.get_side_to_move_key();
     }
 
     fn delete_piece(&mut self, square: usize) {
+        let (color, piece_type) = self.get_piece_on_square(square);
+        self.zobrist_key ^= precomputed_data::ZOBRIST_KEYS.get_piece_square_key(color, piece_type, square);
+        self.delete_piece_unhashed(square);
+    }
+
+    fn create_piece(&mut self, square: usize, color: usize, piece_type: usize) {
+        self.zobrist_key ^= precomputed_data::ZOBRIST_KEYS.get_piece_square_key(color, piece_type, square);
+        self.create_piece_unhashed(square, color, piece_type);
+    }
+
+    /// Same as *delete_piece* but the Zobrist key is left as it is, the undo restores the whole key at the end
+    fn delete_piece_unhashed(&mut self, square: usize) {
         debug_assert!(square < 64, "Square is out of bounds");
         let inverted_bit: u64 = !bitboards::get_bit_from_square(square);
 
         unsafe {
             let (color, piece_type) = self.squares.get_unchecked_mut(square);
-        
+
             *self.pieces.get_unchecked_mut(*color).get_unchecked_mut(0) &= inverted_bit;
             *self.pieces.get_unchecked_mut(*color).get_unchecked_mut(*piece_type) &= inverted_bit;
 
@@ -326,7 +1085,8 @@ impl Board {
         }
     }
 
-    fn create_piece(&mut self, square: usize, color: usize, piece_type: usize) {
+    /// Same as *create_piece* but the Zobrist key is left as it is, the undo restores the whole key at the end
+    fn create_piece_unhashed(&mut self, square: usize, color: usize, piece_type: usize) {
         debug_assert!(square < 64, "Square is out of bounds");
         debug_assert!(piece_type < 7, "Piece type is out of bounds");
         debug_assert!(color < 2, "Color is out of bounds");
@@ -344,95 +1104,219 @@ impl Board {
 
     pub fn make_move(&mut self, move_to_make: Move) -> MoveRecord {
         let old_castling_states = self.castling_states;
+        let old_is_en_passant_possible = self.is_en_passant_possible;
+        let old_en_passant_pawn_square = self.en_passant_pawn_square;
+        let old_en_passant_capture_square = self.en_passant_capture_square;
+        let old_halfmove_clock = self.halfmove_clock;
+        let old_promoted_pieces_bb = self.promoted_pieces_bb;
+        let old_check_counts = self.check_counts;
+        let old_zobrist_key = self.zobrist_key;
+        self.key_history.push(old_zobrist_key);
+
+        // once both colors have lost the castling rights, which is most of the game, they can't change anymore
+        let has_castling_rights = old_castling_states != [0; 2];
+        // the castling and en passant states are hashed back in after the move is made
+        if has_castling_rights {
+            self.zobrist_key ^= self.get_castling_hash();
+        }
+        self.zobrist_key ^= self.get_en_passant_hash();
 
         let current_color = self.get_current_color();
         let opposite_color = self.get_opposite_color();
 
-        let start_square = move_to_make.start_square;
-        let target_square = move_to_make.target_square;
-
-        let (_, mut piece_type) = self.get_piece_on_square(start_square);
+        let start_square = move_to_make.get_start_square();
+        let target_square = move_to_make.get_target_square();
 
-        debug_assert!(piece_type != piece::NONE, "There is no piece on the start square");
-        // deleting the piece from its start square
-        self.delete_piece(start_square);
-
-        let captured_square = move_to_make.capture_square;
-        let (_, captured_piece_type) = self.get_piece_on_square(captured_square);
+        let mut piece_type = if move_to_make.is_drop() {
+            let dropped_piece_type = move_to_make.get_drop_piece_type();
+            self.remove_from_pocket(current_color, dropped_piece_type);
+            dropped_piece_type
+        } else {
+            let (_, piece_type) = self.get_piece_on_square(start_square);
+            debug_assert!(piece_type != piece::NONE, "There is no piece on the start square");
+            // deleting the piece from its start square
+            self.delete_piece(start_square);
+            piece_type
+        };
+
+        let captured_square = move_to_make.get_capture_square();
+        // in Chess960 the start square of the castling rook may be the target square of the king, which is not
+        // a capture, the rook is removed below before the king is placed
+        let captured_piece_type = if move_to_make.is_castling() {
+            piece::NONE
+        } else {
+            self.get_piece_on_square(captured_square).1
+        };
 
         if captured_piece_type != piece::NONE {
             self.delete_piece(captured_square);
         }
 
+        if self.variant.has_pockets() {
+            self.update_pockets_and_promoted_pieces(move_to_make, captured_piece_type);
+        }
+
+        if piece_type == PAWN || captured_piece_type != piece::NONE {
+            self.halfmove_clock = 0;
+        } else {
+            self.halfmove_clock += 1;
+        }
+
+        if current_color == BLACK {
+            self.fullmove_number += 1;
+        }
+
         self.update_en_passant_state(false, INVALID_SQUARE, INVALID_SQUARE);
 
-        let king_side_castling_rook_square = get_king_side_castling_rook_square(current_color);
-        let queen_side_castling_rook_square = get_queen_side_castling_rook_square(current_color);
-
-        match move_to_make.move_type {
-            MoveType::PromotionQueen => piece_type = piece::QUEEN,
-            MoveType::PromotionKnight => piece_type = piece::KNIGHT,
-            MoveType::PromotionRook => piece_type = piece::ROOK,
-            MoveType::PromotionBishop => piece_type = piece::BISHOP,
-            MoveType::PawnDoubleMove => self.update_en_passant_state(true, target_square, (start_square + target_square) / 2),
-            MoveType::CastlingKingSide => {
-                self.delete_piece(king_side_castling_rook_square);
-                self.create_piece(get_castled_king_side_rook_square(current_color), current_color, piece::ROOK);
-            },
-            MoveType::CastlingQueenSide => {
-                self.delete_piece(queen_side_castling_rook_square);
-                self.create_piece(get_castled_queen_side_rook_square(current_color), current_color, piece::ROOK);
-            },
-            _ => (),
+        if move_to_make.is_promotion() {
+            piece_type = move_to_make.get_promotion_piece_type();
+        } else if move_to_make.is_pawn_double_move() {
+            self.update_en_passant_state(true, target_square, (start_square + target_square) / 2);
+        } else if move_to_make.is_castling() {
+            let castling_path = *self.get_castling_path(current_color, move_to_make.get_castling_side());
+            self.delete_piece(castling_path.rook_square);
+            self.create_piece(castling_path.rook_target_square, current_color, piece::ROOK);
         }
 
         self.create_piece(target_square, current_color, piece_type);
 
-        let mut castling_state = self.get_castling_state(current_color);
+        if has_castling_rights {
+            self.annul_castling_rights(current_color, piece_type, start_square, target_square);
+        }
+
+        let exploded_piece_count = if self.variant == Variant::Atomic && captured_piece_type != piece::NONE {
+            self.explode(target_square)
+        } else {
+            0
+        };
+
+        self.switch_color();
+
+        if has_castling_rights {
+            self.zobrist_key ^= self.get_castling_hash();
+        }
+        self.zobrist_key ^= self.get_en_passant_hash();
+
+        if self.variant == Variant::ThreeCheck && self.is_square_attacked(self.get_king_square(opposite_color), current_color) {
+            self.add_check(current_color);
+        }
+
+        debug_assert!(self.zobrist_key == self.compute_hash(), "Zobrist key is out of sync after the move");
+
+        MoveRecord {
+            mov: move_to_make,
+            captured_piece_type: captured_piece_type as u8,
+            is_en_passant_possible: old_is_en_passant_possible,
+            en_passant_pawn_square: old_en_passant_pawn_square as u8,
+            en_passant_capture_square: old_en_passant_capture_square as u8,
+            old_castling_states,
+            old_halfmove_clock,
+            old_promoted_pieces_bb,
+            exploded_piece_count,
+            old_check_counts,
+            old_zobrist_key,
+        }
+    }
+
+    /// Takes the castling rights away when the king or a rook of the moving color leaves its square
+    /// or a rook of the opponent gets captured on its start square
+    fn annul_castling_rights(&mut self, current_color: usize, piece_type: usize, start_square: usize, target_square: usize) {
+        let king_side_rook_square = self.get_castling_path(current_color, castling::KING_SIDE).rook_square;
+        let queen_side_rook_square = self.get_castling_path(current_color, castling::QUEEN_SIDE).rook_square;
+        let castling_state = self.get_castling_state_mut(current_color);
         if piece_type == KING {
-            castling::annul(&mut castling_state);
+            castling::annul(castling_state);
         }
 
         if piece_type == ROOK {
-            if start_square == king_side_castling_rook_square {
-                castling::annul_king_side(&mut castling_state);
+            if start_square == king_side_rook_square {
+                castling::annul_king_side(castling_state);
             }
-            if start_square == queen_side_castling_rook_square {
-                castling::annul_queen_side(&mut castling_state);
+            if start_square == queen_side_rook_square {
+                castling::annul_queen_side(castling_state);
             }
         }
 
-        let mut opposite_castling_state = self.get_castling_state(opposite_color);
-        if target_square == get_king_side_castling_rook_square(opposite_color) {
-            castling::annul_king_side(&mut opposite_castling_state);
-        } 
-        if target_square == get_queen_side_castling_rook_square(opposite_color) {
-            castling::annul_queen_side(&mut opposite_castling_state);
+        let opposite_king_side_rook_square = self.get_castling_path(1 - current_color, castling::KING_SIDE).rook_square;
+        let opposite_queen_side_rook_square = self.get_castling_path(1 - current_color, castling::QUEEN_SIDE).rook_square;
+        let opposite_castling_state = self.get_castling_state_mut(1 - current_color);
+        if target_square == opposite_king_side_rook_square {
+            castling::annul_king_side(opposite_castling_state);
         }
+        if target_square == opposite_queen_side_rook_square {
+            castling::annul_queen_side(opposite_castling_state);
+        }
+    }
 
-        self.switch_color();
+    /// Blows up the capturing piece standing on the capture square and every piece around it except pawns
+    ///
+    /// Returns the number of the exploded pieces, which are pushed to *exploded_pieces*
+    fn explode(&mut self, capture_square: usize) -> u8 {
+        let mut exploded_bb = atomic_move_gen::get_blast_bb(self, capture_square) | bitboards::get_bit_from_square(capture_square);
+        let mut exploded_piece_count = 0;
+
+        while exploded_bb != 0 {
+            let square = bitboards::get_ls1b(exploded_bb);
+            exploded_bb &= exploded_bb - 1;
+
+            let (color, piece_type) = self.get_piece_on_square(square);
+            self.exploded_pieces.push((square, color, piece_type));
+            self.delete_piece(square);
+            exploded_piece_count += 1;
+
+            let king_side_rook_square = self.get_castling_path(color, castling::KING_SIDE).rook_square;
+            let queen_side_rook_square = self.get_castling_path(color, castling::QUEEN_SIDE).rook_square;
+            let castling_state = self.get_castling_state_mut(color);
+            if piece_type == KING {
+                castling::annul(castling_state);
+            } else if piece_type == ROOK && square == king_side_rook_square {
+                castling::annul_king_side(castling_state);
+            } else if piece_type == ROOK && square == queen_side_rook_square {
+                castling::annul_queen_side(castling_state);
+            }
+        }
 
-        MoveRecord::new(
-            move_to_make,
-            captured_piece_type,
-            self.is_en_passant_possible,
-            self.en_passant_pawn_square,
-            self.en_passant_capture_square,
-            old_castling_states
-        )
+        exploded_piece_count
+    }
+
+    /// The captured piece goes to the pocket (as a pawn if it was promoted)
+    /// and the promoted piece marks follow the moving pieces
+    fn update_pockets_and_promoted_pieces(&mut self, mov: Move, captured_piece_type: usize) {
+        let start_bit = bitboards::get_bit_from_square(mov.get_start_square());
+        let target_bit = bitboards::get_bit_from_square(mov.get_target_square());
+        let captured_bit = bitboards::get_bit_from_square(mov.get_capture_square());
+
+        if captured_piece_type != piece::NONE {
+            let is_captured_piece_promoted = self.promoted_pieces_bb & captured_bit != 0;
+            self.add_to_pocket(self.get_current_color(), if is_captured_piece_promoted {PAWN} else {captured_piece_type});
+            self.promoted_pieces_bb &= !captured_bit;
+        }
+
+        if mov.is_promotion() || (!mov.is_drop() && self.promoted_pieces_bb & start_bit != 0) {
+            self.promoted_pieces_bb = (self.promoted_pieces_bb & !start_bit) | target_bit;
+        }
     }
     
     /// Undoes the move from the provided MoveInfo object
     pub fn undo_move(&mut self, move_record: MoveRecord) {
+        for _ in 0..move_record.exploded_piece_count {
+            let (square, color, piece_type) = self.exploded_pieces.pop().expect("The exploded pieces are out of sync with the move records");
+            self.create_piece_unhashed(square, color, piece_type);
+        }
+
         let mov = move_record.mov;
-        let start_square = mov.start_square;
-        let target_square = mov.target_square;
-        let captured_piece_type = move_record.captured_piece_type;
-        let captured_square = mov.capture_square;
+        let start_square = mov.get_start_square();
+        let target_square = mov.get_target_square();
+        let captured_piece_type = move_record.captured_piece_type as usize;
+        let captured_square = mov.get_capture_square();
         self.castling_states = move_record.old_castling_states;
         self.is_en_passant_possible = move_record.is_en_passant_possible;
-        self.en_passant_pawn_square = move_record.en_passant_pawn_square;
-        self.en_passant_capture_square = move_record.en_passant_capture_square;
+        self.en_passant_pawn_square = move_record.en_passant_pawn_square as usize;
+        self.en_passant_capture_square = move_record.en_passant_capture_square as usize;
+        self.halfmove_clock = move_record.old_halfmove_clock;
+        self.check_counts = move_record.old_check_counts;
+        let is_captured_piece_promoted = move_record.old_promoted_pieces_bb & bitboards::get_bit_from_square(captured_square) != 0;
+        self.promoted_pieces_bb = move_record.old_promoted_pieces_bb;
 
         let (_, mut moved_piece_type) = self.get_piece_on_square(target_square);
 
@@ -442,31 +1326,74 @@ impl Board {
 
         let current_color = self.get_current_color();
 
+        if current_color == BLACK {
+            self.fullmove_number -= 1;
+        }
+
         // deleting the piece from the target square
-        self.delete_piece(target_square);
+        self.delete_piece_unhashed(target_square);
 
 
-        match mov.move_type {
-            MoveType::PromotionQueen | MoveType::PromotionKnight | MoveType::PromotionRook | MoveType::PromotionBishop => {
-                moved_piece_type = piece::PAWN;
-            },
-            MoveType::CastlingKingSide => {
-                self.delete_piece(get_castled_king_side_rook_square(current_color));
-                self.create_piece(get_king_side_castling_rook_square(current_color), current_color, piece::ROOK);
-            },
-            MoveType::CastlingQueenSide => {
-                self.delete_piece(get_castled_queen_side_rook_square(current_color));
-                self.create_piece(get_queen_side_castling_rook_square(current_color), current_color, piece::ROOK);
-            },
-            _ => (),
+        if mov.is_promotion() {
+            moved_piece_type = piece::PAWN;
+        } else if mov.is_castling() {
+            let castling_path = *self.get_castling_path(current_color, mov.get_castling_side());
+            self.delete_piece_unhashed(castling_path.rook_target_square);
+            self.create_piece_unhashed(castling_path.rook_square, current_color, piece::ROOK);
         }
 
-        self.create_piece(start_square, current_color, moved_piece_type);
+        if mov.is_drop() {
+            self.add_to_pocket(current_color, moved_piece_type);
+        } else {
+            self.create_piece_unhashed(start_square, current_color, moved_piece_type);
+        }
 
-        if captured_piece_type == piece::NONE {
-            return;
+        if captured_piece_type != piece::NONE {
+            self.create_piece_unhashed(captured_square, self.get_opposite_color(), captured_piece_type);
+            if self.variant.has_pockets() {
+                self.remove_from_pocket(current_color, if is_captured_piece_promoted {PAWN} else {captured_piece_type});
+            }
         }
 
-        self.create_piece(captured_square, self.get_opposite_color(), captured_piece_type, );
+        self.zobrist_key = move_record.old_zobrist_key;
+        self.key_history.pop();
+        debug_assert!(self.zobrist_key == self.compute_hash(), "Zobrist key is out of sync after undoing the move");
+    }
+
+    /// Passes the turn to the opponent without moving, used by the search for things like the null move pruning
+    ///
+    /// The side to move must not be in check
+    pub fn make_null_move(&mut self) -> NullMoveRecord {
+        debug_assert!(!move_gen::is_in_check(self), "Null move can't be made in check");
+
+        let null_move_record = NullMoveRecord {
+            is_en_passant_possible: self.is_en_passant_possible,
+            en_passant_pawn_square: self.en_passant_pawn_square,
+            en_passant_capture_square: self.en_passant_capture_square,
+            old_halfmove_clock: self.halfmove_clock,
+            old_zobrist_key: self.zobrist_key,
+        };
+        self.key_history.push(self.zobrist_key);
+
+        self.zobrist_key ^= self.get_en_passant_hash();
+        self.update_en_passant_state(false, INVALID_SQUARE, INVALID_SQUARE);
+        self.halfmove_clock += 1;
+        self.switch_color();
+
+        debug_assert!(self.zobrist_key == self.compute_hash(), "Zobrist key is out of sync after the null move");
+        null_move_record
     }
-}
\ No newline at end of file
+
+    /// Undoes the null move from the provided NullMoveRecord object
+    pub fn undo_null_move(&mut self, null_move_record: NullMoveRecord) {
+        self.switch_color();
+        self.is_en_passant_possible = null_move_record.is_en_passant_possible;
+        self.en_passant_pawn_square = null_move_record.en_passant_pawn_square;
+        self.en_passant_capture_square = null_move_record.en_passant_capture_square;
+        self.halfmove_clock = null_move_record.old_halfmove_clock;
+
+        self.zobrist_key = null_move_record.old_zobrist_key;
+        self.key_history.pop();
+        debug_assert!(self.zobrist_key == self.compute_hash(), "Zobrist key is out of sync after undoing the null move");
+    }
+}
@@ -1,6 +1,6 @@
 /* Contains all values either constant or calculated on init, which are used only for better expierence 
 on the user side like more human representation of the squares (using *e1* instead of *3*) etc. */
-use crate::{bitboards, board::Board, piece};
+use crate::{bitboards, board::{self, Board}, chess_parse_error::ChessParseError, piece, variant::Variant};
 use phf::phf_map;
 
 // Decorative strings
@@ -13,10 +13,6 @@ const OCCUPIED_SQUARE_ON_BITBOARD:  &str = "1";
 // Game positions
 /// Position the regular chess game starts from
 pub const DEFAULT_FEN: &str             = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
-/// Position used for pseudo-legal move generation testing
-pub const PERFT_FEN: &str               = "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8";
-/// Position used for legal move generation and move search testing
-pub const MATE_IN_2_FEN: &str           = "kbK5/pp6/1P6/8/8/8/8/R7 w - -";
 
 pub const ROW_TO_BOARD_LETTER: [char; 8] = ['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h'];
 /// Outputs a letter on the board from the provided row number, for user's comfort
@@ -41,16 +37,17 @@ static FEN_SYM_TO_PIECE: phf::Map<char, (usize, usize)> = phf_map! {
 };
 /// outputs a corresponding piece to the FEN character provided
 pub fn get_piece_from_fen(fen_sym: &char) -> &(usize, usize) {
-    match FEN_SYM_TO_PIECE.get(fen_sym) {
-        Some(received_piece) => {
-            return received_piece;
-        },
-        None => {
-            panic!("Something went wrong with pieces' names");
-        }
+    match try_get_piece_from_fen(fen_sym) {
+        Ok(received_piece) => received_piece,
+        Err(error) => panic!("{}", error),
     }
 }
 
+/// Same as *get_piece_from_fen* but returns an error for an unknown character
+pub fn try_get_piece_from_fen(fen_sym: &char) -> Result<&'static (usize, usize), ChessParseError> {
+    FEN_SYM_TO_PIECE.get(fen_sym).ok_or(ChessParseError::UnknownPieceChar(*fen_sym))
+}
+
 const SQUARE_TO_NAME: [&'static str; 64] = [
     "h1", "g1", "f1", "e1", "d1", "c1", "b1", "a1",
     "h2", "g2", "f2", "e2", "d2", "c2", "b2", "a2",
@@ -78,16 +75,19 @@ static NAME_TO_SQUARE: phf::Map<&'static str, usize> = phf_map! {
 };
 /// Outputs a corresponding square to the provided square name
 pub fn get_square_from_name(square_name: &str) -> usize {
-    match NAME_TO_SQUARE.get(square_name) {
-        Some(square) => {
-            return *square;
-        },
-        None => {
-            panic!("wrong indexation");
-        }
+    match try_get_square_from_name(square_name) {
+        Ok(square) => square,
+        Err(error) => panic!("{}", error),
     }
 }
 
+/// Same as *get_square_from_name* but returns an error for an invalid name
+pub fn try_get_square_from_name(square_name: &str) -> Result<usize, ChessParseError> {
+    NAME_TO_SQUARE.get(square_name)
+        .copied()
+        .ok_or_else(|| ChessParseError::InvalidSquare(square_name.to_string()))
+}
+
 const PIECE_NAMES: [[char; 6]; 2] = [
     [ 'K', 'P', 'N', 'B', 'R', 'Q' ],
     [ 'k', 'p', 'n', 'b', 'r', 'q' ]
@@ -136,4 +136,17 @@ pub fn print_board(board: &Board) {
         println!("   {}", DECORATIVE_ROW);
     }
     println!("   {}", DECORATIVE_LETTERS_ROW);
+
+    if board.get_variant().has_pockets() {
+        for (color, color_name) in [(piece::WHITE, "White"), (piece::BLACK, "Black")] {
+            let pocket: String = board::POCKET_PIECE_TYPES.iter()
+                .flat_map(|&piece_type| std::iter::repeat_n(piece_to_fen_sym(color, piece_type), board.get_pocket_count(color, piece_type)))
+                .collect();
+            println!("{} pocket: {}", color_name, pocket);
+        }
+    }
+
+    if board.get_variant() == Variant::ThreeCheck {
+        println!("Checks given: White {}, Black {}", board.get_check_count(piece::WHITE), board.get_check_count(piece::BLACK));
+    }
 }
\ No newline at end of file
@@ -1,3 +1,8 @@
+/// Bit of the castling state responsible for the king side castling
+pub const KING_SIDE: u8 = 0b01;
+/// Bit of the castling state responsible for the queen side castling
+pub const QUEEN_SIDE: u8 = 0b10;
+
 pub fn can_king_side(castling_state: u8) -> bool {
     castling_state & 0b01 == 0b01
 }
@@ -20,4 +25,47 @@ pub fn annul_queen_side(castling_state: &mut u8) {
 
 pub fn annul(castling_state: &mut u8) {
     *castling_state = 0;
-}
\ No newline at end of file
+}
+
+/// Returns the index of the castling side in the arrays like *Board::castling_paths*
+pub fn get_side_index(castling_side: u8) -> usize {
+    debug_assert!(castling_side == KING_SIDE || castling_side == QUEEN_SIDE, "Invalid castling side");
+    (castling_side >> 1) as usize
+}
+
+/// Returns every square of the rank between the provided squares, both of them included
+const fn get_rank_segment_mask(square_1: usize, square_2: usize) -> u64 {
+    let (low_square, high_square) = if square_1 < square_2 {(square_1, square_2)} else {(square_2, square_1)};
+    (u64::MAX >> (63 - high_square)) & (u64::MAX << low_square)
+}
+
+/// Squares involved in a single castling
+///
+/// In the regular chess they are always the same, but in Chess960 the king and the rooks
+/// can start on any file, while the king and the rook still end up on the g/f or c/d files
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CastlingPath {
+    pub rook_square: usize,
+    pub king_target_square: usize,
+    pub rook_target_square: usize,
+    /// Squares that must be empty, except for the castling king and rook themselves
+    pub empty_mask: u64,
+    /// Squares the king goes through including its target, none of them can be attacked
+    pub king_path_mask: u64,
+}
+
+impl CastlingPath {
+    pub const fn new(king_square: usize, rook_square: usize, king_target_square: usize, rook_target_square: usize) -> Self {
+        let king_and_rook_bb = (1 << king_square) | (1 << rook_square);
+        let king_path_mask = get_rank_segment_mask(king_square, king_target_square) & !(1 << king_square);
+
+        Self {
+            rook_square,
+            king_target_square,
+            rook_target_square,
+            empty_mask: (get_rank_segment_mask(king_square, king_target_square)
+                | get_rank_segment_mask(rook_square, rook_target_square)) & !king_and_rook_bb,
+            king_path_mask,
+        }
+    }
+}
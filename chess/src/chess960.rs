@@ -0,0 +1,87 @@
+/* Generates the 960 starting positions of Fischer Random chess, numbered with the Scharnagl scheme
+(https://en.wikipedia.org/wiki/Fischer_random_chess_numbering_scheme) */
+use rand::{rng, Rng};
+
+use crate::board::Board;
+
+/// Number of the different Chess960 starting positions
+pub const CHESS960_POSITION_COUNT: u16 = 960;
+/// Scharnagl index of the regular chess starting position (*RNBQKBNR*)
+pub const CLASSICAL_CHESS960_INDEX: u16 = 518;
+
+/// Placements of the two knights on the five squares left after the bishops and the queen
+const KNIGHT_PLACEMENTS: [(usize, usize); 10] = [
+    (0, 1), (0, 2), (0, 3), (0, 4),
+    (1, 2), (1, 3), (1, 4),
+    (2, 3), (2, 4),
+    (3, 4),
+];
+
+/// Returns the pieces of the white back rank from the a-file to the h-file in FEN notation
+pub fn get_chess960_back_rank(index: u16) -> [char; 8] {
+    debug_assert!(index < CHESS960_POSITION_COUNT, "Chess960 index out of bounds");
+
+    let mut back_rank = [' '; 8];
+    let mut index = index as usize;
+
+    // light squared bishop goes to b, d, f or h, dark squared one to a, c, e or g
+    back_rank[(index % 4) * 2 + 1] = 'B';
+    index /= 4;
+    back_rank[(index % 4) * 2] = 'B';
+    index /= 4;
+
+    let queen_index = index % 6;
+    index /= 6;
+    let queen_file = (0..8).filter(|&file| back_rank[file] == ' ').nth(queen_index).unwrap();
+    back_rank[queen_file] = 'Q';
+
+    let empty_files: Vec<usize> = (0..8).filter(|&file| back_rank[file] == ' ').collect();
+    let (first_knight, second_knight) = KNIGHT_PLACEMENTS[index];
+    back_rank[empty_files[first_knight]] = 'N';
+    back_rank[empty_files[second_knight]] = 'N';
+
+    // the king always stands between the rooks
+    let empty_files: Vec<usize> = (0..8).filter(|&file| back_rank[file] == ' ').collect();
+    for (file, piece) in empty_files.into_iter().zip(['R', 'K', 'R']) {
+        back_rank[file] = piece;
+    }
+
+    back_rank
+}
+
+/// Returns the FEN of the Chess960 starting position, the castling rights are written in Shredder-FEN
+pub fn get_chess960_fen(index: u16) -> String {
+    let back_rank = get_chess960_back_rank(index);
+    let white_back_rank: String = back_rank.iter().collect();
+    let black_back_rank = white_back_rank.to_ascii_lowercase();
+
+    let rook_files: String = back_rank.iter()
+        .enumerate()
+        .filter(|(_, &piece)| piece == 'R')
+        .map(|(file, _)| (b'a' + file as u8) as char)
+        .rev()
+        .collect();
+
+    format!(
+        "{}/pppppppp/8/8/8/8/PPPPPPPP/{} w {}{} - 0 1",
+        black_back_rank,
+        white_back_rank,
+        rook_files.to_ascii_uppercase(),
+        rook_files,
+    )
+}
+
+/// Returns the board of the Chess960 starting position, it is marked as a Chess960 game even for the index 518
+pub fn get_chess960_board(index: u16) -> Board {
+    let mut board = Board::from_fen(&get_chess960_fen(index));
+    board.set_chess960(true);
+    board
+}
+
+pub fn get_random_chess960_index() -> u16 {
+    rng().random_range(0..CHESS960_POSITION_COUNT)
+}
+
+pub fn get_random_chess960_board() -> Board {
+    get_chess960_board(get_random_chess960_index())
+}
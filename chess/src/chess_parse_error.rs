@@ -0,0 +1,71 @@
+use std::{error::Error, fmt::Display};
+
+/// Describes what exactly was wrong with a FEN or EPD string, a square name or a move
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChessParseError {
+    /// The FEN string ended before the provided field
+    MissingFenField(&'static str),
+    /// The FEN string has more fields than the standard allows
+    UnexpectedFenField(String),
+    /// The piece placement field doesn't contain exactly 8 ranks
+    WrongRankCount(usize),
+    /// The provided rank (1-8) doesn't describe exactly 8 squares
+    WrongRankLength { rank: usize, length: usize },
+    UnknownPieceChar(char),
+    InvalidSideToMove(String),
+    InvalidCastlingField(String),
+    InvalidEnPassantField(String),
+    InvalidHalfmoveClock(String),
+    InvalidFullmoveNumber(String),
+    InvalidSquare(String),
+    /// The promotion letter of a UCI move isn't one of *q*, *r*, *b*, *n*
+    InvalidPromotion(char),
+    /// The piece letter of a UCI or SAN drop isn't one of *P*, *N*, *B*, *R*, *Q*
+    InvalidDropPiece(char),
+    /// The Crazyhouse pocket contains a king, an unknown piece or too many pieces of a type
+    InvalidPocket(String),
+    /// The Three-check field isn't written as *+W+B* with at most 3 checks for each color
+    InvalidCheckCount(String),
+    /// A UCI move must be 4 or 5 characters long
+    InvalidUciLength(usize),
+    /// The string doesn't follow the Standard Algebraic Notation
+    InvalidSan(String),
+    /// The SAN move is well-formed but no legal move in the position matches it
+    IllegalSanMove(String),
+    /// More than one legal move matches the SAN move
+    AmbiguousSanMove(String),
+    /// An EPD operation isn't written as *opcode operands;* or has an unterminated string
+    InvalidEpdOperation(String),
+    /// The operands don't match the type the opcode expects
+    InvalidEpdOperand { opcode: String, operand: String },
+}
+
+impl Display for ChessParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingFenField(field) => write!(f, "missing FEN field: {}", field),
+            Self::UnexpectedFenField(field) => write!(f, "unexpected FEN field: \"{}\"", field),
+            Self::WrongRankCount(count) => write!(f, "expected 8 ranks in the piece placement, found {}", count),
+            Self::WrongRankLength { rank, length } => write!(f, "rank {} describes {} squares instead of 8", rank, length),
+            Self::UnknownPieceChar(sym) => write!(f, "unknown piece character: '{}'", sym),
+            Self::InvalidSideToMove(field) => write!(f, "invalid side to move: \"{}\"", field),
+            Self::InvalidCastlingField(field) => write!(f, "invalid castling field: \"{}\"", field),
+            Self::InvalidEnPassantField(field) => write!(f, "invalid en passant field: \"{}\"", field),
+            Self::InvalidHalfmoveClock(field) => write!(f, "invalid halfmove clock: \"{}\"", field),
+            Self::InvalidFullmoveNumber(field) => write!(f, "invalid fullmove number: \"{}\"", field),
+            Self::InvalidSquare(name) => write!(f, "invalid square: \"{}\"", name),
+            Self::InvalidPromotion(sym) => write!(f, "invalid promotion piece: '{}'", sym),
+            Self::InvalidDropPiece(sym) => write!(f, "invalid drop piece: '{}'", sym),
+            Self::InvalidPocket(pocket) => write!(f, "invalid pocket: \"{}\"", pocket),
+            Self::InvalidCheckCount(field) => write!(f, "invalid check count field: \"{}\"", field),
+            Self::InvalidUciLength(length) => write!(f, "a UCI move must be 4 or 5 characters long, found {}", length),
+            Self::InvalidSan(san) => write!(f, "invalid SAN move: \"{}\"", san),
+            Self::IllegalSanMove(san) => write!(f, "illegal SAN move: \"{}\"", san),
+            Self::AmbiguousSanMove(san) => write!(f, "ambiguous SAN move: \"{}\"", san),
+            Self::InvalidEpdOperation(operation) => write!(f, "invalid EPD operation: \"{}\"", operation),
+            Self::InvalidEpdOperand { opcode, operand } => write!(f, "invalid operand of the EPD opcode {}: \"{}\"", opcode, operand),
+        }
+    }
+}
+
+impl Error for ChessParseError {}
@@ -9,4 +9,21 @@ pub fn get_magic_json_path() -> &'static str {
     }
 }
 
-pub const PERFT_DEPTH: u8 = 4;
\ No newline at end of file
+pub const PERFT_DEPTH: u8 = 4;
+
+/// EPD suite of positions with known perft node counts
+pub const PERFT_SUITE_FILE: &str = "perft_suite.epd";
+/// EPD suite of Atomic positions with known perft node counts
+pub const ATOMIC_PERFT_SUITE_FILE: &str = "atomic_perft_suite.epd";
+/// EPD suite of Antichess positions with known perft node counts
+pub const ANTICHESS_PERFT_SUITE_FILE: &str = "antichess_perft_suite.epd";
+/// EPD suite of positions with a forced mate
+pub const MATE_SUITE_FILE: &str = "mate_suite.epd";
+
+pub fn get_test_suite_path(file_name: &str) -> String {
+    if Path::new("chess/test_suites").exists() {
+        format!("chess/test_suites/{}", file_name)
+    } else {
+        format!("test_suites/{}", file_name)
+    }
+}
\ No newline at end of file
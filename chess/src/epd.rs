@@ -0,0 +1,292 @@
+use std::{collections::HashMap, fmt::Display, fs, path::Path};
+
+use crate::{board::Board, chess_parse_error::ChessParseError, r#move::Move, variant::Variant};
+
+/// The typed value of a single EPD operation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EpdOperand {
+    /// Moves of the *bm*, *am*, *pm* and *sm* opcodes, resolved from SAN
+    Moves(Vec<Move>),
+    /// Strings of the *id*, *c0*-*c9*, *v0*-*v9* and *eco* opcodes
+    Text(String),
+    /// Numbers of the *acd*, *acn*, *acs*, *ce*, *dm*, *hmvc*, *fmvn* and *D1*-*D9* opcodes
+    Integer(i64),
+    /// Operands of the opcodes that aren't known, kept as they were written
+    Tokens(Vec<String>),
+}
+
+/// A single line of an EPD file: a position without the move counters followed by
+/// operations like *bm Qxf7+; id "WAC.001";*
+#[derive(Clone)]
+pub struct EpdRecord {
+    board: Board,
+    operations: HashMap<String, EpdOperand>,
+}
+
+fn is_move_opcode(opcode: &str) -> bool {
+    matches!(opcode, "bm" | "am" | "pm" | "sm")
+}
+
+fn is_text_opcode(opcode: &str) -> bool {
+    matches!(opcode, "id" | "eco")
+        || (opcode.len() == 2 && (opcode.starts_with('c') || opcode.starts_with('v')) && opcode.ends_with(|sym: char| sym.is_ascii_digit()))
+}
+
+fn is_integer_opcode(opcode: &str) -> bool {
+    matches!(opcode, "acd" | "acn" | "acs" | "ce" | "dm" | "hmvc" | "fmvn")
+        || get_perft_depth(opcode).is_some()
+}
+
+/// Returns the depth of the perft node count opcode (*D1*-*D9*)
+fn get_perft_depth(opcode: &str) -> Option<u8> {
+    opcode.strip_prefix('D')
+        .filter(|depth| depth.len() == 1)
+        .and_then(|depth| depth.parse().ok())
+        .filter(|&depth| depth > 0)
+}
+
+/// Returns the first whitespace separated field and the text after it
+fn split_first_field(text: &str) -> (&str, &str) {
+    let text = text.trim_start();
+    let field_end = text.find(char::is_whitespace).unwrap_or(text.len());
+    text.split_at(field_end)
+}
+
+/// Splits the operations on the semicolons outside of quoted strings
+fn split_operations(operations: &str) -> Vec<&str> {
+    let mut result = Vec::new();
+    let mut operation_start = 0;
+    let mut is_in_quotes = false;
+
+    for (index, sym) in operations.char_indices() {
+        match sym {
+            '"' => is_in_quotes = !is_in_quotes,
+            ';' if !is_in_quotes => {
+                result.push(operations[operation_start..index].trim());
+                operation_start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    result.push(operations[operation_start..].trim());
+
+    result.into_iter().filter(|operation| !operation.is_empty()).collect()
+}
+
+/// Splits the operation into the opcode and the operands, a quoted string is a single operand
+fn split_operation_tokens(operation: &str) -> Result<Vec<String>, ChessParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = operation.chars().peekable();
+
+    while let Some(&sym) = chars.peek() {
+        if sym.is_whitespace() {
+            chars.next();
+        } else if sym == '"' {
+            chars.next();
+            let token: String = chars.by_ref().take_while(|&sym| sym != '"').collect();
+            tokens.push(token);
+        } else {
+            let mut token = String::new();
+            while let Some(&sym) = chars.peek() {
+                if sym.is_whitespace() || sym == '"' {
+                    break;
+                }
+                token.push(sym);
+                chars.next();
+            }
+            tokens.push(token);
+        }
+    }
+
+    if operation.chars().filter(|&sym| sym == '"').count() % 2 != 0 {
+        return Err(ChessParseError::InvalidEpdOperation(operation.to_string()));
+    }
+    Ok(tokens)
+}
+
+impl EpdRecord {
+    /// Parses a single EPD line
+    ///
+    /// The FEN move counters written right after the four position fields are accepted too,
+    /// since a lot of perft suites are stored like that
+    pub fn parse(epd: &str) -> Result<Self, ChessParseError> {
+        let mut rest = epd;
+        let mut fen_fields = Vec::with_capacity(4);
+        for field_name in ["piece placement", "side to move", "castling", "en passant"] {
+            let (field, remaining) = split_first_field(rest);
+            if field.is_empty() {
+                return Err(ChessParseError::MissingFenField(field_name));
+            }
+            fen_fields.push(field);
+            rest = remaining;
+        }
+
+        let mut raw_operations = Vec::new();
+        let mut move_counters = None;
+        for (index, operation) in split_operations(rest).into_iter().enumerate() {
+            let mut tokens = split_operation_tokens(operation)?;
+            let opcode = tokens.remove(0);
+
+            if opcode.starts_with(|sym: char| sym.is_ascii_digit()) {
+                if index != 0 || tokens.len() != 1 {
+                    return Err(ChessParseError::InvalidEpdOperation(operation.to_string()));
+                }
+                move_counters = Some(format!("{} {}", opcode, tokens[0]));
+                continue;
+            }
+            raw_operations.push((opcode, tokens));
+        }
+
+        let get_raw_operand = |opcode: &str| raw_operations.iter()
+            .find(|(raw_opcode, _)| raw_opcode == opcode)
+            .and_then(|(_, operands)| operands.first().cloned());
+        let move_counters = move_counters.unwrap_or_else(|| format!(
+            "{} {}",
+            get_raw_operand("hmvc").unwrap_or(String::from("0")),
+            get_raw_operand("fmvn").unwrap_or(String::from("1")),
+        ));
+
+        let board = Board::try_from_fen(&format!("{} {}", fen_fields.join(" "), move_counters))?;
+
+        let mut operations = HashMap::with_capacity(raw_operations.len());
+        for (opcode, operands) in raw_operations {
+            let invalid_operand = || ChessParseError::InvalidEpdOperand { opcode: opcode.clone(), operand: operands.join(" ") };
+
+            let operand = if is_move_opcode(&opcode) {
+                EpdOperand::Moves(operands.iter()
+                    .map(|san| board.parse_san(san))
+                    .collect::<Result<Vec<Move>, ChessParseError>>()?)
+            } else if is_text_opcode(&opcode) {
+                EpdOperand::Text(operands.join(" "))
+            } else if is_integer_opcode(&opcode) {
+                match operands.as_slice() {
+                    [number] => EpdOperand::Integer(number.parse().map_err(|_| invalid_operand())?),
+                    _ => return Err(invalid_operand()),
+                }
+            } else {
+                EpdOperand::Tokens(operands)
+            };
+            operations.insert(opcode, operand);
+        }
+
+        Ok(Self {
+            board,
+            operations,
+        })
+    }
+
+    pub fn get_board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Switches the position to the variant, EPD has no field for it
+    ///
+    /// The moves of the operations were parsed with the standard rules and are kept as they are
+    pub fn set_variant(&mut self, variant: Variant) {
+        self.board.set_variant(variant);
+    }
+
+    pub fn get_operations(&self) -> &HashMap<String, EpdOperand> {
+        &self.operations
+    }
+
+    pub fn get_operation(&self, opcode: &str) -> Option<&EpdOperand> {
+        self.operations.get(opcode)
+    }
+
+    fn get_moves(&self, opcode: &str) -> Option<&[Move]> {
+        match self.operations.get(opcode) {
+            Some(EpdOperand::Moves(moves)) => Some(moves),
+            _ => None,
+        }
+    }
+
+    fn get_text(&self, opcode: &str) -> Option<&str> {
+        match self.operations.get(opcode) {
+            Some(EpdOperand::Text(text)) => Some(text),
+            _ => None,
+        }
+    }
+
+    fn get_integer(&self, opcode: &str) -> Option<i64> {
+        match self.operations.get(opcode) {
+            Some(EpdOperand::Integer(number)) => Some(*number),
+            _ => None,
+        }
+    }
+
+    /// Returns the moves of the *bm* opcode, any of them solves the position
+    pub fn get_best_moves(&self) -> Option<&[Move]> {
+        self.get_moves("bm")
+    }
+
+    /// Returns the moves of the *am* opcode, none of them should be played
+    pub fn get_avoid_moves(&self) -> Option<&[Move]> {
+        self.get_moves("am")
+    }
+
+    pub fn get_id(&self) -> Option<&str> {
+        self.get_text("id")
+    }
+
+    /// Returns the comment of the *c0*-*c9* opcode
+    pub fn get_comment(&self, index: u8) -> Option<&str> {
+        debug_assert!(index < 10, "Comment index out of bounds");
+        self.get_text(&format!("c{}", index))
+    }
+
+    /// Returns the depth the position was analysed to (*acd*)
+    pub fn get_analysis_depth(&self) -> Option<i64> {
+        self.get_integer("acd")
+    }
+
+    /// Returns the number of moves to the mate (*dm*)
+    pub fn get_direct_mate(&self) -> Option<i64> {
+        self.get_integer("dm")
+    }
+
+    /// Returns the expected perft node counts of the *D1*-*D9* opcodes sorted by depth
+    pub fn get_perft_counts(&self) -> Vec<(u8, u64)> {
+        let mut perft_counts: Vec<(u8, u64)> = self.operations.iter()
+            .filter_map(|(opcode, operand)| match (get_perft_depth(opcode), operand) {
+                (Some(depth), EpdOperand::Integer(nodes)) => Some((depth, *nodes as u64)),
+                _ => None,
+            })
+            .collect();
+        perft_counts.sort_unstable();
+        perft_counts
+    }
+}
+
+/// Describes why an EPD file couldn't be loaded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EpdFileError {
+    Io(String),
+    /// The record on the provided line (starting from 1) is invalid
+    InvalidRecord { line: usize, error: ChessParseError },
+}
+
+impl Display for EpdFileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "failed to read EPD file: {}", error),
+            Self::InvalidRecord { line, error } => write!(f, "line {}: {}", line, error),
+        }
+    }
+}
+
+impl std::error::Error for EpdFileError {}
+
+/// Reads every record of the EPD text, empty lines and lines starting with *#* are skipped
+pub fn parse_epd_records(epd: &str) -> Result<Vec<EpdRecord>, EpdFileError> {
+    epd.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(index, line)| EpdRecord::parse(line).map_err(|error| EpdFileError::InvalidRecord { line: index + 1, error }))
+        .collect()
+}
+
+pub fn load_epd_file(path: impl AsRef<Path>) -> Result<Vec<EpdRecord>, EpdFileError> {
+    let epd = fs::read_to_string(path).map_err(|error| EpdFileError::Io(error.to_string()))?;
+    parse_epd_records(&epd)
+}
@@ -0,0 +1,165 @@
+/* Every number the evaluation uses, kept in one place so they can be tuned. The piece-square tables are written
+the way the board is seen by white, a8 first and h1 last, and get mirrored for black */
+use crate::piece::{self, BISHOP, KNIGHT, PAWN, QUEEN, ROOK};
+
+use super::tapered_score::TaperedScore;
+
+/// Parameters of the evaluation, every array is indexed by the piece type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvalParams {
+    pub piece_values: [TaperedScore; 7],
+    /// Goes like *midgame_psts\[piece_type]\[index]*, see *get_pst_index*
+    pub midgame_psts: [[i32; 64]; 7],
+    pub endgame_psts: [[i32; 64]; 7],
+    /// How much every piece left on the board adds to the game phase, the phase of the starting position is the midgame
+    pub phase_weights: [i32; 7],
+    pub bishop_pair: TaperedScore,
+    /// Rook on a file without any pawns
+    pub rook_open_file: TaperedScore,
+    /// Rook on a file with only the enemy pawns
+    pub rook_semi_open_file: TaperedScore,
+    /// Bonus of the side to move for being able to make the next move
+    pub tempo: TaperedScore,
+}
+
+/// Returns the index of the square in the piece-square tables for the provided color
+pub fn get_pst_index(color: usize, square: usize) -> usize {
+    // the squares go from h1 to a8 while the tables go from a8 to h1, black sees the ranks the other way round
+    if color == piece::WHITE {square ^ 63} else {square ^ 7}
+}
+
+impl EvalParams {
+    /// Returns the phase of the starting position, anything with more material is treated as the midgame as well
+    pub fn get_max_phase(&self) -> i32 {
+        2 * (8 * self.phase_weights[PAWN] + 2 * self.phase_weights[KNIGHT] + 2 * self.phase_weights[BISHOP]
+            + 2 * self.phase_weights[ROOK] + self.phase_weights[QUEEN])
+    }
+}
+
+impl Default for EvalParams {
+    fn default() -> Self {
+        DEFAULT_EVAL_PARAMS
+    }
+}
+
+const EMPTY_PST: [i32; 64] = [0; 64];
+
+const PAWN_MIDGAME_PST: [i32; 64] = [
+     0,   0,   0,   0,   0,   0,   0,   0,
+    50,  50,  50,  50,  50,  50,  50,  50,
+    10,  10,  20,  30,  30,  20,  10,  10,
+     5,   5,  10,  25,  25,  10,   5,   5,
+     0,   0,   0,  20,  20,   0,   0,   0,
+     5,  -5, -10,   0,   0, -10,  -5,   5,
+     5,  10,  10, -20, -20,  10,  10,   5,
+     0,   0,   0,   0,   0,   0,   0,   0,
+];
+
+const PAWN_ENDGAME_PST: [i32; 64] = [
+     0,   0,   0,   0,   0,   0,   0,   0,
+    80,  80,  80,  80,  80,  80,  80,  80,
+    50,  50,  50,  50,  50,  50,  50,  50,
+    30,  30,  30,  30,  30,  30,  30,  30,
+    20,  20,  20,  20,  20,  20,  20,  20,
+    10,  10,  10,  10,  10,  10,  10,  10,
+    10,  10,  10,  10,  10,  10,  10,  10,
+     0,   0,   0,   0,   0,   0,   0,   0,
+];
+
+const KNIGHT_PST: [i32; 64] = [
+   -50, -40, -30, -30, -30, -30, -40, -50,
+   -40, -20,   0,   0,   0,   0, -20, -40,
+   -30,   0,  10,  15,  15,  10,   0, -30,
+   -30,   5,  15,  20,  20,  15,   5, -30,
+   -30,   0,  15,  20,  20,  15,   0, -30,
+   -30,   5,  10,  15,  15,  10,   5, -30,
+   -40, -20,   0,   5,   5,   0, -20, -40,
+   -50, -40, -30, -30, -30, -30, -40, -50,
+];
+
+const BISHOP_PST: [i32; 64] = [
+   -20, -10, -10, -10, -10, -10, -10, -20,
+   -10,   0,   0,   0,   0,   0,   0, -10,
+   -10,   0,   5,  10,  10,   5,   0, -10,
+   -10,   5,   5,  10,  10,   5,   5, -10,
+   -10,   0,  10,  10,  10,  10,   0, -10,
+   -10,  10,  10,  10,  10,  10,  10, -10,
+   -10,   5,   0,   0,   0,   0,   5, -10,
+   -20, -10, -10, -10, -10, -10, -10, -20,
+];
+
+const ROOK_MIDGAME_PST: [i32; 64] = [
+     0,   0,   0,   0,   0,   0,   0,   0,
+     5,  10,  10,  10,  10,  10,  10,   5,
+    -5,   0,   0,   0,   0,   0,   0,  -5,
+    -5,   0,   0,   0,   0,   0,   0,  -5,
+    -5,   0,   0,   0,   0,   0,   0,  -5,
+    -5,   0,   0,   0,   0,   0,   0,  -5,
+    -5,   0,   0,   0,   0,   0,   0,  -5,
+     0,   0,   0,   5,   5,   0,   0,   0,
+];
+
+const ROOK_ENDGAME_PST: [i32; 64] = [
+     5,   5,   5,   5,   5,   5,   5,   5,
+    10,  10,  10,  10,  10,  10,  10,  10,
+     0,   0,   0,   0,   0,   0,   0,   0,
+     0,   0,   0,   0,   0,   0,   0,   0,
+     0,   0,   0,   0,   0,   0,   0,   0,
+     0,   0,   0,   0,   0,   0,   0,   0,
+     0,   0,   0,   0,   0,   0,   0,   0,
+     0,   0,   0,   0,   0,   0,   0,   0,
+];
+
+const QUEEN_PST: [i32; 64] = [
+   -20, -10, -10,  -5,  -5, -10, -10, -20,
+   -10,   0,   0,   0,   0,   0,   0, -10,
+   -10,   0,   5,   5,   5,   5,   0, -10,
+    -5,   0,   5,   5,   5,   5,   0,  -5,
+     0,   0,   5,   5,   5,   5,   0,  -5,
+   -10,   5,   5,   5,   5,   5,   0, -10,
+   -10,   0,   5,   0,   0,   0,   0, -10,
+   -20, -10, -10,  -5,  -5, -10, -10, -20,
+];
+
+/// The king hides behind the pawns in the midgame
+const KING_MIDGAME_PST: [i32; 64] = [
+   -30, -40, -40, -50, -50, -40, -40, -30,
+   -30, -40, -40, -50, -50, -40, -40, -30,
+   -30, -40, -40, -50, -50, -40, -40, -30,
+   -30, -40, -40, -50, -50, -40, -40, -30,
+   -20, -30, -30, -40, -40, -30, -30, -20,
+   -10, -20, -20, -20, -20, -20, -20, -10,
+    20,  20,   0,   0,   0,   0,  20,  20,
+    20,  30,  10,   0,   0,  10,  30,  20,
+];
+
+/// The king goes to the center in the endgame
+const KING_ENDGAME_PST: [i32; 64] = [
+   -50, -40, -30, -20, -20, -30, -40, -50,
+   -30, -20, -10,   0,   0, -10, -20, -30,
+   -30, -10,  20,  30,  30,  20, -10, -30,
+   -30, -10,  30,  40,  40,  30, -10, -30,
+   -30, -10,  30,  40,  40,  30, -10, -30,
+   -30, -10,  20,  30,  30,  20, -10, -30,
+   -30, -30,   0,   0,   0,   0, -30, -30,
+   -50, -30, -30, -30, -30, -30, -30, -50,
+];
+
+pub const DEFAULT_EVAL_PARAMS: EvalParams = EvalParams {
+    piece_values: [
+        TaperedScore::ZERO,
+        TaperedScore::ZERO,
+        TaperedScore::new(100, 120),
+        TaperedScore::new(320, 300),
+        TaperedScore::new(330, 320),
+        TaperedScore::new(500, 540),
+        TaperedScore::new(900, 960),
+    ],
+    midgame_psts: [EMPTY_PST, KING_MIDGAME_PST, PAWN_MIDGAME_PST, KNIGHT_PST, BISHOP_PST, ROOK_MIDGAME_PST, QUEEN_PST],
+    endgame_psts: [EMPTY_PST, KING_ENDGAME_PST, PAWN_ENDGAME_PST, KNIGHT_PST, BISHOP_PST, ROOK_ENDGAME_PST, QUEEN_PST],
+    phase_weights: [0, 0, 0, 1, 1, 2, 4],
+    bishop_pair: TaperedScore::new(30, 50),
+    rook_open_file: TaperedScore::new(25, 10),
+    rook_semi_open_file: TaperedScore::new(10, 5),
+    tempo: TaperedScore::new(15, 5),
+};
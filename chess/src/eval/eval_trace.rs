@@ -0,0 +1,132 @@
+use std::fmt::Display;
+
+use crate::piece;
+
+use super::tapered_score::TaperedScore;
+
+/// Part of the evaluation that is traced separately
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvalTerm {
+    /// The pieces on the board and in the Crazyhouse pockets
+    Material,
+    PieceSquares,
+    BishopPair,
+    /// Rooks on the open and the semi-open files
+    RookFiles,
+    Tempo,
+}
+
+impl EvalTerm {
+    pub const ALL: [EvalTerm; 5] = [EvalTerm::Material, EvalTerm::PieceSquares, EvalTerm::BishopPair, EvalTerm::RookFiles, EvalTerm::Tempo];
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            EvalTerm::Material => "Material",
+            EvalTerm::PieceSquares => "Piece squares",
+            EvalTerm::BishopPair => "Bishop pair",
+            EvalTerm::RookFiles => "Rook files",
+            EvalTerm::Tempo => "Tempo",
+        }
+    }
+}
+
+/// Score of every term for both colors, the evaluation is always made through it
+/// so the printed trace can't differ from the score the search gets
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvalTrace {
+    /// Goes like *term_scores\[term]\[color]*, from the point of view of each color
+    term_scores: [[TaperedScore; 2]; EvalTerm::ALL.len()],
+    phase: i32,
+    max_phase: i32,
+    current_color: usize,
+    /// Antichess is won by losing the pieces, so the final score is negated
+    is_inverted: bool,
+}
+
+impl EvalTrace {
+    pub fn new(phase: i32, max_phase: i32, current_color: usize, is_inverted: bool) -> Self {
+        Self {
+            term_scores: [[TaperedScore::ZERO; 2]; EvalTerm::ALL.len()],
+            phase,
+            max_phase,
+            current_color,
+            is_inverted,
+        }
+    }
+
+    pub fn add(&mut self, term: EvalTerm, color: usize, score: TaperedScore) {
+        debug_assert!(color < 2, "Color out of bounds");
+        self.term_scores[term as usize][color] += score;
+    }
+
+    pub fn get_term_score(&self, term: EvalTerm, color: usize) -> TaperedScore {
+        debug_assert!(color < 2, "Color out of bounds");
+        self.term_scores[term as usize][color]
+    }
+
+    /// Returns the difference of the term between white and black
+    pub fn get_term_balance(&self, term: EvalTerm) -> TaperedScore {
+        self.get_term_score(term, piece::WHITE) - self.get_term_score(term, piece::BLACK)
+    }
+
+    /// Returns the phase from zero (endgame) to *get_max_phase* (midgame)
+    pub fn get_phase(&self) -> i32 {
+        self.phase
+    }
+
+    pub fn get_max_phase(&self) -> i32 {
+        self.max_phase
+    }
+
+    /// Returns the sum of every term from white's point of view, before the tapering
+    pub fn get_total(&self) -> TaperedScore {
+        EvalTerm::ALL.iter().fold(TaperedScore::ZERO, |total, &term| total + self.get_term_balance(term))
+    }
+
+    /// Returns the final score from white's point of view
+    pub fn get_white_score(&self) -> i32 {
+        let score = self.get_total().taper(self.phase, self.max_phase);
+        if self.is_inverted {-score} else {score}
+    }
+
+    /// Returns the final score from the side to move's point of view
+    pub fn get_score(&self) -> i32 {
+        let white_score = self.get_white_score();
+        if self.current_color == piece::WHITE {white_score} else {-white_score}
+    }
+}
+
+impl Display for EvalTrace {
+    /// Prints the table of the terms like the *eval* command of Stockfish, the scores are in centipawns
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const SEPARATOR: &str = "+---------------+-------------+-------------+-------------+";
+
+        writeln!(f, "{}", SEPARATOR)?;
+        writeln!(f, "|          Term |    White    |    Black    |    Total    |")?;
+        writeln!(f, "|               |   MG    EG  |   MG    EG  |   MG    EG  |")?;
+        writeln!(f, "{}", SEPARATOR)?;
+        for term in EvalTerm::ALL {
+            let white_score = self.get_term_score(term, piece::WHITE);
+            let black_score = self.get_term_score(term, piece::BLACK);
+            let balance = self.get_term_balance(term);
+            writeln!(
+                f, "| {:>13} | {:>5} {:>5} | {:>5} {:>5} | {:>5} {:>5} |",
+                term.get_name(),
+                white_score.midgame, white_score.endgame,
+                black_score.midgame, black_score.endgame,
+                balance.midgame, balance.endgame,
+            )?;
+        }
+        writeln!(f, "{}", SEPARATOR)?;
+
+        let total = self.get_total();
+        writeln!(f, "| {:>13} |             |             | {:>5} {:>5} |", "Total", total.midgame, total.endgame)?;
+        writeln!(f, "{}", SEPARATOR)?;
+        writeln!(f)?;
+        writeln!(f, "Phase: {} / {}", self.phase, self.max_phase)?;
+        if self.is_inverted {
+            writeln!(f, "The score is negated since the pieces have to be lost")?;
+        }
+        write!(f, "Final evaluation: {} (white side), {} (side to move)", self.get_white_score(), self.get_score())
+    }
+}
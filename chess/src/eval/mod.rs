@@ -0,0 +1,105 @@
+/* Hand-crafted evaluation. Every term is scored separately for the midgame and the endgame, the two scores
+are then blended by the game phase, which depends on the pieces left on the board. The score is from
+the side to move's point of view and is the same for a position and its color-flipped version */
+use crate::{bitboards, board::{self, Board}, piece, precomputed_data::square_data, variant::Variant};
+
+pub mod eval_params;
+pub mod eval_trace;
+pub mod tapered_score;
+
+use eval_params::{get_pst_index, EvalParams, DEFAULT_EVAL_PARAMS};
+use eval_trace::{EvalTerm, EvalTrace};
+use tapered_score::TaperedScore;
+
+/// Every square of the h-file, shifted by the file to get the others
+const H_FILE_MASK: u64 = 0x0101010101010101;
+
+const PIECE_TYPES: [usize; 6] = [piece::KING, piece::PAWN, piece::KNIGHT, piece::BISHOP, piece::ROOK, piece::QUEEN];
+
+/// Returns the game phase of the position, the pieces in the pockets don't count
+fn get_phase(board: &Board, params: &EvalParams) -> i32 {
+    let phase: i32 = PIECE_TYPES.iter()
+        .map(|&piece_type| params.phase_weights[piece_type] * board.get_piece_type_bitboard(piece_type).count_ones() as i32)
+        .sum();
+    phase.min(params.get_max_phase())
+}
+
+fn evaluate_material(board: &Board, params: &EvalParams, color: usize) -> TaperedScore {
+    let mut score = TaperedScore::ZERO;
+    for piece_type in PIECE_TYPES {
+        score += params.piece_values[piece_type] * board.get_piece_bitboard(color, piece_type).count_ones() as i32;
+    }
+    if board.get_variant().has_pockets() {
+        for piece_type in board::POCKET_PIECE_TYPES {
+            score += params.piece_values[piece_type] * board.get_pocket_count(color, piece_type) as i32;
+        }
+    }
+    score
+}
+
+fn evaluate_piece_squares(board: &Board, params: &EvalParams, color: usize) -> TaperedScore {
+    let mut score = TaperedScore::ZERO;
+    for piece_type in PIECE_TYPES {
+        let mut piece_bb = board.get_piece_bitboard(color, piece_type);
+        while piece_bb != 0 {
+            let pst_index = get_pst_index(color, bitboards::get_ls1b(piece_bb));
+            piece_bb &= piece_bb - 1;
+            score += TaperedScore::new(params.midgame_psts[piece_type][pst_index], params.endgame_psts[piece_type][pst_index]);
+        }
+    }
+    score
+}
+
+fn evaluate_bishop_pair(board: &Board, params: &EvalParams, color: usize) -> TaperedScore {
+    if board.get_piece_bitboard(color, piece::BISHOP).count_ones() >= 2 {params.bishop_pair} else {TaperedScore::ZERO}
+}
+
+fn evaluate_rook_files(board: &Board, params: &EvalParams, color: usize) -> TaperedScore {
+    let own_pawns_bb = board.get_piece_bitboard(color, piece::PAWN);
+    let opposite_pawns_bb = board.get_piece_bitboard(1 - color, piece::PAWN);
+    let mut score = TaperedScore::ZERO;
+
+    let mut rook_bb = board.get_piece_bitboard(color, piece::ROOK);
+    while rook_bb != 0 {
+        let file_mask = H_FILE_MASK << square_data::get_file_from_square(bitboards::get_ls1b(rook_bb));
+        rook_bb &= rook_bb - 1;
+
+        if own_pawns_bb & file_mask == 0 {
+            score += if opposite_pawns_bb & file_mask == 0 {params.rook_open_file} else {params.rook_semi_open_file};
+        }
+    }
+    score
+}
+
+/// Evaluates the position term by term with the provided parameters, *EvalTrace::get_score* gives the final score
+pub fn trace(board: &Board, params: &EvalParams) -> EvalTrace {
+    let current_color = board.get_current_color();
+    let mut trace = EvalTrace::new(get_phase(board, params), params.get_max_phase(), current_color, board.get_variant() == Variant::Antichess);
+
+    for color in [piece::WHITE, piece::BLACK] {
+        trace.add(EvalTerm::Material, color, evaluate_material(board, params, color));
+        trace.add(EvalTerm::PieceSquares, color, evaluate_piece_squares(board, params, color));
+        trace.add(EvalTerm::BishopPair, color, evaluate_bishop_pair(board, params, color));
+        trace.add(EvalTerm::RookFiles, color, evaluate_rook_files(board, params, color));
+    }
+    trace.add(EvalTerm::Tempo, current_color, params.tempo);
+
+    trace
+}
+
+/// Returns the score of the position from the side to move's point of view with the provided parameters
+pub fn evaluate(board: &Board, params: &EvalParams) -> i32 {
+    trace(board, params).get_score()
+}
+
+impl Board {
+    /// Returns the score of the position in centipawns from the side to move's point of view
+    pub fn evaluate(&self) -> i32 {
+        evaluate(self, &DEFAULT_EVAL_PARAMS)
+    }
+
+    /// Returns every term of the evaluation, printing it gives a table like the *eval* command of Stockfish
+    pub fn trace_evaluation(&self) -> EvalTrace {
+        trace(self, &DEFAULT_EVAL_PARAMS)
+    }
+}
@@ -0,0 +1,60 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+/// Pair of a midgame and an endgame score, blended together by the game phase at the end of the evaluation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TaperedScore {
+    pub midgame: i32,
+    pub endgame: i32,
+}
+
+impl TaperedScore {
+    pub const ZERO: Self = Self::new(0, 0);
+
+    pub const fn new(midgame: i32, endgame: i32) -> Self {
+        Self { midgame, endgame }
+    }
+
+    /// Blends the scores by the phase, *max_phase* gives the midgame score and zero the endgame one
+    pub fn taper(&self, phase: i32, max_phase: i32) -> i32 {
+        debug_assert!((0..=max_phase).contains(&phase), "Phase out of bounds");
+        (self.midgame * phase + self.endgame * (max_phase - phase)) / max_phase
+    }
+}
+
+impl Add for TaperedScore {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.midgame + other.midgame, self.endgame + other.endgame)
+    }
+}
+
+impl AddAssign for TaperedScore {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for TaperedScore {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.midgame - other.midgame, self.endgame - other.endgame)
+    }
+}
+
+impl Neg for TaperedScore {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.midgame, -self.endgame)
+    }
+}
+
+impl Mul<i32> for TaperedScore {
+    type Output = Self;
+
+    fn mul(self, factor: i32) -> Self {
+        Self::new(self.midgame * factor, self.endgame * factor)
+    }
+}
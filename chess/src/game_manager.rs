@@ -1,9 +1,12 @@
-use core::game::GameState;
+use core::game::{GameState, Termination};
 
-use crate::{board::Board, board_representation, move_generation::{attack_calculator::AttackCalculator, move_gen}, piece, player::Player};
+use crate::{board::Board, board_representation, r#move::Move, move_generation::{move_gen, move_list::CrazyhouseMoveList}, pgn::pgn_game::PgnGame, piece, player::Player, variant::Variant};
 
 pub struct GameManager {
     board: Board,
+    starting_board: Board,
+    /// Every move made since the start, used for the PGN export
+    moves: Vec<Move>,
     players: [Box<dyn Player>; 2],
     game_state: GameState
 }
@@ -11,34 +14,88 @@ pub struct GameManager {
 impl GameManager {
     pub fn new(players: [Box<dyn Player>; 2], fen_position: &str) -> Self {
         let board = Board::from_fen(fen_position);
+        if let Err(violations) = board.validate() {
+            let descriptions: Vec<String> = violations.iter().map(|violation| violation.to_string()).collect();
+            panic!("Illegal starting position \"{}\": {}", fen_position, descriptions.join(", "));
+        }
 
         Self {
+            starting_board: board.clone(),
             board,
+            moves: Vec::new(),
             players,
             game_state: GameState::InProgress
         }
     }
 
+    pub fn get_game_state(&self) -> GameState {
+        self.game_state
+    }
+
+    /// Returns the game played so far, ready to be written as PGN
+    pub fn get_pgn_game(&self) -> PgnGame {
+        PgnGame::from_moves(&self.starting_board, &self.moves, self.game_state)
+    }
+
     pub fn run(&mut self) {
         loop {
             board_representation::print_board(&self.board);
 
-            let attack_calculator = AttackCalculator::new(&self.board);
-            let mut possible_moves = Vec::with_capacity(218);
-            move_gen::generate_moves(&mut possible_moves, &mut self.board);
+            if let Some(game_state) = self.board.get_variant_game_state() {
+                match game_state {
+                    GameState::WhiteWon(termination) => println!("White wins by {}!", termination),
+                    GameState::BlackWon(termination) => println!("Black wins by {}!", termination),
+                    GameState::Draw(termination) => println!("Draw by {}!", termination),
+                    GameState::InProgress => {}
+                }
+                self.game_state = game_state;
+                break;
+            }
+
+            let mut possible_moves = CrazyhouseMoveList::new();
+            move_gen::generate_moves(&mut possible_moves, &self.board);
 
             if possible_moves.is_empty() {
-                if attack_calculator.in_check() {
-                    println!("Checkmate! {} wins!", self.board.get_current_color());
-                    self.game_state = if self.board.get_current_color() == piece::WHITE { GameState::BlackWon } else { GameState::WhiteWon };
+                if self.board.get_variant() == Variant::Antichess {
+                    // the side that can't move wins in Antichess
+                    self.game_state = if self.board.get_current_color() == piece::WHITE {
+                        GameState::WhiteWon(Termination::Stalemate)
+                    } else {
+                        GameState::BlackWon(Termination::Stalemate)
+                    };
+                    println!("Stalemate! {} wins!", if self.board.get_current_color() == piece::WHITE {"White"} else {"Black"});
+                } else if move_gen::is_in_check(&self.board) {
+                    self.game_state = if self.board.get_current_color() == piece::WHITE { 
+                        GameState::BlackWon(Termination::Checkmate) 
+                    } else { 
+                        GameState::WhiteWon(Termination::Checkmate) 
+                    };
+                    println!("Checkmate! {} wins!", if self.board.get_current_color() == piece::WHITE {"Black"} else {"White"});
                 } else {
                     println!("Stalemate!");
-                    self.game_state = GameState::Draw;
+                    self.game_state = GameState::Draw(Termination::Stalemate);
                 }
                 break;
             }
 
-            self.board.make_move(self.players[self.board.get_current_color()].get_move(&self.board));
+            if let Some(draw_reason) = self.board.get_draw_reason() {
+                println!("Draw by {}!", draw_reason);
+                self.game_state = GameState::Draw(draw_reason);
+                break;
+            }
+            if let Some(draw_reason) = self.board.get_claimable_draw_reason() {
+                println!("A draw can be claimed by {}.", draw_reason);
+            }
+
+            let mov = loop {
+                let mov = self.players[self.board.get_current_color()].get_move(&self.board);
+                match self.board.validate_move(&mov) {
+                    Ok(()) => break mov,
+                    Err(reason) => println!("Illegal move {} ({}). Please try again.", mov, reason),
+                }
+            };
+            self.board.make_move(mov);
+            self.moves.push(mov);
         }
     }
 }
\ No newline at end of file
@@ -2,11 +2,21 @@ pub mod piece;
 pub mod r#move;
 pub mod board;
 pub mod castling;
+pub mod chess960;
 pub mod board_representation;
+pub mod chess_parse_error;
 pub mod config;
 pub mod precomputed_data;
 pub mod bitboards;
 pub mod move_generation;
 pub mod game_manager;
 pub mod player;
-pub mod perft;
\ No newline at end of file
+pub mod perft;
+pub mod pgn;
+pub mod position_violation;
+pub mod epd;
+pub mod see;
+pub mod attacks;
+pub mod eval;
+pub mod search;
+pub mod variant;
\ No newline at end of file
@@ -0,0 +1,49 @@
+use std::{error::Error, fmt::Display};
+
+use crate::{board_representation::{get_square_name, piece_to_fen_sym}, piece};
+
+/// Describes why a move can't be made in the position
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IllegalMoveReason {
+    /// The game is already won by a rule of the variant, like the third check
+    GameOver,
+    NoPieceOnStartSquare { square: usize },
+    OpponentPiece { square: usize },
+    /// The piece can't reach the target square the way it moves
+    InvalidPieceMove { piece_type: usize, start_square: usize, target_square: usize },
+    /// A pawn reaching the last rank must be promoted, and only a pawn reaching it can be
+    InvalidPromotion,
+    /// The castling right is lost, the path is blocked or the king passes through an attacked square
+    CastlingNotAllowed,
+    /// Drops are only possible in Crazyhouse, with a piece from the pocket to an empty square
+    InvalidDrop,
+    /// The king is left in check or moves into one, in Atomic also when the own king would explode
+    KingLeftInCheck,
+    /// Antichess doesn't allow other moves if there is a capture
+    CaptureRequired,
+}
+
+impl Display for IllegalMoveReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::GameOver => write!(f, "the game is over"),
+            Self::NoPieceOnStartSquare { square } =>
+                write!(f, "there is no piece on {}", get_square_name(*square)),
+            Self::OpponentPiece { square } =>
+                write!(f, "the piece on {} belongs to the opponent", get_square_name(*square)),
+            Self::InvalidPieceMove { piece_type, start_square, target_square } => write!(
+                f, "{} on {} can't move to {}",
+                piece_to_fen_sym(piece::WHITE, *piece_type),
+                get_square_name(*start_square),
+                get_square_name(*target_square)
+            ),
+            Self::InvalidPromotion => write!(f, "invalid promotion"),
+            Self::CastlingNotAllowed => write!(f, "castling is not allowed"),
+            Self::InvalidDrop => write!(f, "invalid drop"),
+            Self::KingLeftInCheck => write!(f, "the king is left in check"),
+            Self::CaptureRequired => write!(f, "a capture is required"),
+        }
+    }
+}
+
+impl Error for IllegalMoveReason {}
@@ -0,0 +1,225 @@
+/* Checks a single move against the position without generating every legal move, used for the moves
+that don't come from the move generation, like the human input or the hash moves of the search */
+use crate::{bitboards, board::{self, Board}, castling, move_generation::{antichess_move_gen, atomic_move_gen, attack_calculator::AttackCalculator, move_gen, pseudo_legal_move_gen}, piece, precomputed_data, variant::Variant};
+
+use super::{illegal_move_reason::IllegalMoveReason, move_record::MoveRecord, Move, MoveType, UciMove};
+
+impl Board {
+    pub fn is_legal(&self, mov: &Move) -> bool {
+        self.validate_move(mov).is_ok()
+    }
+
+    /// Same as *is_legal* but describes why the move is illegal
+    pub fn validate_move(&self, mov: &Move) -> Result<(), IllegalMoveReason> {
+        if self.get_variant_game_state().is_some() {
+            return Err(IllegalMoveReason::GameOver);
+        }
+
+        if let MoveType::Drop(piece_type) = mov.get_move_type() {
+            return self.validate_drop(mov, piece_type);
+        }
+
+        let start_square = mov.get_start_square();
+        let (color, piece_type) = self.get_piece_on_square(start_square);
+        if piece_type == piece::NONE {
+            return Err(IllegalMoveReason::NoPieceOnStartSquare { square: start_square });
+        }
+        if color != self.get_current_color() {
+            return Err(IllegalMoveReason::OpponentPiece { square: start_square });
+        }
+
+        if mov.is_castling() {
+            return self.validate_castling(mov, piece_type);
+        }
+
+        self.validate_piece_move(mov, piece_type)?;
+
+        match self.get_variant() {
+            Variant::Atomic => self.validate_explosion(mov),
+            Variant::Antichess => self.validate_forced_capture(mov),
+            _ => self.validate_king_safety(mov, piece_type),
+        }
+    }
+
+    /// Makes the move if it is legal, the move type is taken from the position like in *Move::from_uci*
+    pub fn try_make_move(&mut self, mov: UciMove) -> Result<MoveRecord, IllegalMoveReason> {
+        let mov = Move::from_uci(mov, self);
+        self.validate_move(&mov)?;
+        Ok(self.make_move(mov))
+    }
+
+    /// Checks that the piece can reach the target square, ignoring the pins and checks
+    fn validate_piece_move(&self, mov: &Move, piece_type: usize) -> Result<(), IllegalMoveReason> {
+        let current_color = self.get_current_color();
+        let start_square = mov.get_start_square();
+        let target_square = mov.get_target_square();
+        let target_square_bb = bitboards::get_bit_from_square(target_square);
+        let all_occ = self.get_all_occupied_squares();
+        let invalid_piece_move = Err(IllegalMoveReason::InvalidPieceMove { piece_type, start_square, target_square });
+
+        if self.get_all_occupied_squares_for_color(current_color) & target_square_bb != 0 {
+            return invalid_piece_move;
+        }
+
+        if piece_type != piece::PAWN {
+            if mov.is_promotion() {
+                return Err(IllegalMoveReason::InvalidPromotion);
+            }
+            if mov.get_move_type() != MoveType::Regular
+            || pseudo_legal_move_gen::get_attacks_bb(piece_type, start_square, all_occ) & target_square_bb == 0 {
+                return invalid_piece_move;
+            }
+            return Ok(());
+        }
+
+        let shift_amount = move_gen::get_pawn_shift_amount(current_color) as i32;
+        let one_square_target = start_square as i32 + shift_amount;
+        let pawn_attacks_bb = precomputed_data::SQUARE_DATA.get_bb_for_pawn_attacks(current_color, start_square);
+
+        let is_valid = match mov.get_move_type() {
+            MoveType::PawnDoubleMove => {
+                move_gen::get_pawn_start_rank(current_color) & bitboards::get_bit_from_square(start_square) != 0
+                && target_square as i32 == one_square_target + shift_amount
+                && all_occ & (bitboards::get_bit_from_square(one_square_target as usize) | target_square_bb) == 0
+            },
+            MoveType::EnPassant => {
+                self.is_en_passant_possible()
+                && target_square == self.en_passant_capture_square()
+                && pawn_attacks_bb & target_square_bb != 0
+            },
+            _ => {
+                (target_square as i32 == one_square_target && all_occ & target_square_bb == 0)
+                || pawn_attacks_bb & target_square_bb & self.get_all_occupied_squares_for_color(1 - current_color) != 0
+            },
+        };
+
+        if !is_valid {
+            return invalid_piece_move;
+        }
+
+        let is_promotion_rank = move_gen::get_pawn_promotion_rank(current_color) & target_square_bb != 0;
+        if mov.is_promotion() != is_promotion_rank
+        || (mov.get_move_type() == MoveType::PromotionKing && self.get_variant().is_king_royal()) {
+            return Err(IllegalMoveReason::InvalidPromotion);
+        }
+
+        Ok(())
+    }
+
+    /// Checks the pins and checks of the move that the piece can already make
+    fn validate_king_safety(&self, mov: &Move, piece_type: usize) -> Result<(), IllegalMoveReason> {
+        let attack_calculator = AttackCalculator::new(self);
+        let start_square = mov.get_start_square();
+        let start_square_bb = bitboards::get_bit_from_square(start_square);
+        let target_square_bb = bitboards::get_bit_from_square(mov.get_target_square());
+
+        if piece_type == piece::KING {
+            return if attack_calculator.squares_in_attack_bb & target_square_bb != 0 {
+                Err(IllegalMoveReason::KingLeftInCheck)
+            } else {
+                Ok(())
+            };
+        }
+
+        if attack_calculator.is_in_double_check {
+            return Err(IllegalMoveReason::KingLeftInCheck);
+        }
+
+        let check_block_bb = attack_calculator.check_block_bb;
+        if check_block_bb != 0 {
+            // the en passant capture removes the checking pawn without standing on its square
+            let blocks_check = if mov.is_en_passant() {
+                bitboards::get_bit_from_square(self.en_passant_pawn_square()) & check_block_bb != 0
+            } else {
+                target_square_bb & check_block_bb != 0
+            };
+            if !blocks_check {
+                return Err(IllegalMoveReason::KingLeftInCheck);
+            }
+        }
+
+        if mov.is_en_passant() && attack_calculator.forbidden_en_passant_square == start_square {
+            return Err(IllegalMoveReason::KingLeftInCheck);
+        }
+
+        // a pinned piece can only move along the pin
+        for (direction_index, pin_bb) in attack_calculator.pins_bbs.iter().enumerate() {
+            if pin_bb & start_square_bb != 0
+            && precomputed_data::SQUARE_DATA.get_file_rank_diagonal_mask(start_square, direction_index) & target_square_bb == 0 {
+                return Err(IllegalMoveReason::KingLeftInCheck);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_castling(&self, mov: &Move, piece_type: usize) -> Result<(), IllegalMoveReason> {
+        let current_color = self.get_current_color();
+        let castling_side = if mov.get_move_type() == MoveType::CastlingKingSide {castling::KING_SIDE} else {castling::QUEEN_SIDE};
+
+        if !self.get_variant().has_castling()
+        || piece_type != piece::KING
+        || mov.get_target_square() != self.get_castling_path(current_color, castling_side).king_target_square {
+            return Err(IllegalMoveReason::CastlingNotAllowed);
+        }
+
+        if self.get_variant() == Variant::Atomic {
+            return if atomic_move_gen::can_castle(self, current_color, mov.get_start_square(), castling_side) {
+                Ok(())
+            } else {
+                Err(IllegalMoveReason::CastlingNotAllowed)
+            };
+        }
+
+        let attack_calculator = AttackCalculator::new(self);
+        if attack_calculator.in_check()
+        || !move_gen::can_castle(self, current_color, mov.get_start_square(), castling_side, attack_calculator.squares_in_attack_bb, self.get_all_occupied_squares()) {
+            return Err(IllegalMoveReason::CastlingNotAllowed);
+        }
+
+        Ok(())
+    }
+
+    fn validate_drop(&self, mov: &Move, piece_type: usize) -> Result<(), IllegalMoveReason> {
+        let target_square = mov.get_target_square();
+        let target_square_bb = bitboards::get_bit_from_square(target_square);
+
+        if !self.get_variant().has_pockets()
+        || mov.get_start_square() != target_square
+        || !board::POCKET_PIECE_TYPES.contains(&piece_type)
+        || self.get_pocket_count(self.get_current_color(), piece_type) == 0
+        || self.get_all_occupied_squares() & target_square_bb != 0
+        || (piece_type == piece::PAWN && (bitboards::FIRST_RANK_MASK | bitboards::EIGHTH_RANK_MASK) & target_square_bb != 0) {
+            return Err(IllegalMoveReason::InvalidDrop);
+        }
+
+        let attack_calculator = AttackCalculator::new(self);
+        if attack_calculator.is_in_double_check
+        || (attack_calculator.check_block_bb != 0 && attack_calculator.check_block_bb & target_square_bb == 0) {
+            return Err(IllegalMoveReason::KingLeftInCheck);
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the Atomic move neither explodes the own king nor leaves it attacked,
+    /// unless the enemy king explodes first
+    fn validate_explosion(&self, mov: &Move) -> Result<(), IllegalMoveReason> {
+        let current_color = self.get_current_color();
+        if atomic_move_gen::is_legal(self, *mov, current_color, self.get_king_square(current_color)) {
+            Ok(())
+        } else {
+            Err(IllegalMoveReason::KingLeftInCheck)
+        }
+    }
+
+    /// Checks that the Antichess move is a capture if there is any
+    fn validate_forced_capture(&self, mov: &Move) -> Result<(), IllegalMoveReason> {
+        let is_capture = self.get_piece_on_square(mov.get_capture_square()).1 != piece::NONE;
+        if !is_capture && antichess_move_gen::has_capture(self) {
+            Err(IllegalMoveReason::CaptureRequired)
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -1,22 +1,55 @@
+pub mod illegal_move_reason;
+mod legality;
 pub mod move_record;
+mod san;
 
 use std::fmt::Display;
 
-use crate::{bitboards, board::{self, Board}, board_representation::{get_piece_from_fen, get_square_from_name, get_square_name, piece_to_fen_sym}, piece};
+use crate::{bitboards, board::{self, Board}, board_representation::{get_square_name, piece_to_fen_sym, try_get_square_from_name}, castling, chess_parse_error::ChessParseError, piece};
 
-// #[derive(Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MoveType {
     Regular,
     PawnDoubleMove,
+    /// The captured pawn stands next to the start square, behind the target square
+    EnPassant,
     PromotionQueen,
     PromotionKnight,
     PromotionRook,
     PromotionBishop,
+    /// Only allowed in Antichess, where the king is an ordinary piece
+    PromotionKing,
     CastlingKingSide,
-    CastlingQueenSide
+    CastlingQueenSide,
+    /// Crazyhouse drop of the provided piece type from the pocket, the start square is the target square
+    Drop(usize),
 }
 
-// #[derive(Clone)]
+/// Returns the piece type of the drop letter (*P*, *N*, *B*, *R*, *Q*), the case is ignored
+pub fn get_drop_piece_from_sym(sym: char) -> Result<usize, ChessParseError> {
+    match sym.to_ascii_uppercase() {
+        'P' => Ok(piece::PAWN),
+        'N' => Ok(piece::KNIGHT),
+        'B' => Ok(piece::BISHOP),
+        'R' => Ok(piece::ROOK),
+        'Q' => Ok(piece::QUEEN),
+        _ => Err(ChessParseError::InvalidDropPiece(sym)),
+    }
+}
+
+/// Returns the piece type the pawn promotes to, *piece::NONE* if the move type isn't a promotion
+pub fn get_promotion_piece(move_type: MoveType) -> usize {
+    match move_type {
+        MoveType::PromotionQueen => piece::QUEEN,
+        MoveType::PromotionKnight => piece::KNIGHT,
+        MoveType::PromotionRook => piece::ROOK,
+        MoveType::PromotionBishop => piece::BISHOP,
+        MoveType::PromotionKing => piece::KING,
+        _ => piece::NONE,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct UciMove {
     move_type: MoveType,
     start_square: usize,
@@ -39,91 +72,247 @@ impl UciMove {
 
     /// Accepts a UCI move (e.g e2e4, e7e8q) as a string and returns a UciMove if the move is valid
     pub fn from_uci(uci: &str) -> UciMoveCreationResult {
-        if uci.len() >= 4 && uci.len() <= 5 {
-            let start_square = get_square_from_name(&uci[0..2]);
-            let target_square = get_square_from_name(&uci[2..4]);
-            let mut move_type = MoveType::Regular;
-            if uci.len() == 5 {
-                let promotion = get_piece_from_fen(
-                    &uci.chars().nth(4).expect("Something with move from uci")
-                ).1;
-
-                move_type = match promotion {
-                    piece::QUEEN => MoveType::PromotionQueen,
-                    piece::KNIGHT => MoveType::PromotionKnight,
-                    piece::ROOK => MoveType::PromotionRook,
-                    piece::BISHOP => MoveType::PromotionBishop,
-                    _ => MoveType::Regular
-                };
-            }
-            return UciMoveCreationResult::Success(UciMove::new(move_type, start_square, target_square));
+        match Self::try_from_uci(uci) {
+            Ok(mov) => UciMoveCreationResult::Success(mov),
+            Err(_) => UciMoveCreationResult::Failure,
         }
-        UciMoveCreationResult::Failure
+    }
+
+    /// Same as *from_uci* but describes what is wrong with the provided move
+    pub fn try_from_uci(uci: &str) -> Result<Self, ChessParseError> {
+        let length = uci.chars().count();
+        if !(4..=5).contains(&length) {
+            return Err(ChessParseError::InvalidUciLength(length));
+        }
+
+        // Crazyhouse drops are written as *P@e4*
+        if let Some((piece_sym, target_square_name)) = uci.split_once('@') {
+            let mut piece_syms = piece_sym.chars();
+            let piece_type = match (piece_syms.next(), piece_syms.next()) {
+                (Some(sym), None) => get_drop_piece_from_sym(sym)?,
+                _ => return Err(ChessParseError::InvalidDropPiece('@')),
+            };
+            let target_square = try_get_square_from_name(target_square_name)?;
+            return Ok(UciMove::new(MoveType::Drop(piece_type), target_square, target_square));
+        }
+
+        let start_square_name = uci.get(0..2).ok_or_else(|| ChessParseError::InvalidSquare(uci.to_string()))?;
+        let target_square_name = uci.get(2..4).ok_or_else(|| ChessParseError::InvalidSquare(uci.to_string()))?;
+        let start_square = try_get_square_from_name(start_square_name)?;
+        let target_square = try_get_square_from_name(target_square_name)?;
+
+        let mut move_type = MoveType::Regular;
+        if let Some(promotion_sym) = uci.chars().nth(4) {
+            move_type = match promotion_sym.to_ascii_lowercase() {
+                'q' => MoveType::PromotionQueen,
+                'n' => MoveType::PromotionKnight,
+                'r' => MoveType::PromotionRook,
+                'b' => MoveType::PromotionBishop,
+                'k' => MoveType::PromotionKing,
+                _ => return Err(ChessParseError::InvalidPromotion(promotion_sym)),
+            };
+        }
+
+        Ok(UciMove::new(move_type, start_square, target_square))
     }
 
     pub fn is_promotion(&self) -> bool {
         match &self.move_type {
-            MoveType::PromotionQueen | MoveType::PromotionKnight | MoveType::PromotionRook | MoveType::PromotionBishop => true,
+            MoveType::PromotionQueen | MoveType::PromotionKnight | MoveType::PromotionRook | MoveType::PromotionBishop
+            | MoveType::PromotionKing => true,
             _ => false
         }
     }
 }
 
-pub struct Move {
-    pub start_square: usize,
-    pub target_square: usize,
-    pub capture_square: usize,
-    pub move_type: MoveType,
+// layout of the packed move: bits 0-5 are the start square, 6-11 the target square, 12-15 the flags
+const SQUARE_MASK: u16 = 0b111111;
+const TARGET_SQUARE_SHIFT: u16 = 6;
+const FLAGS_SHIFT: u16 = 12;
+
+// flags of the packed move, one for each move type
+const REGULAR_FLAG: u16 = 0;
+const PAWN_DOUBLE_MOVE_FLAG: u16 = 1;
+const EN_PASSANT_FLAG: u16 = 2;
+const CASTLING_KING_SIDE_FLAG: u16 = 3;
+const CASTLING_QUEEN_SIDE_FLAG: u16 = 4;
+const PROMOTION_QUEEN_FLAG: u16 = 5;
+const PROMOTION_KNIGHT_FLAG: u16 = 6;
+const PROMOTION_ROOK_FLAG: u16 = 7;
+const PROMOTION_BISHOP_FLAG: u16 = 8;
+const PROMOTION_KING_FLAG: u16 = 9;
+/// The drops take the flags from 10 to 14, one for each piece type from the pawn to the queen
+const DROP_FLAG: u16 = 10;
+const LAST_DROP_FLAG: u16 = DROP_FLAG + (piece::QUEEN - piece::PAWN) as u16;
+/// Piece types of the promotion flags, starting from *PROMOTION_QUEEN_FLAG*
+const PROMOTION_PIECES: [usize; 5] = [piece::QUEEN, piece::KNIGHT, piece::ROOK, piece::BISHOP, piece::KING];
+
+impl MoveType {
+    fn to_flags(self) -> u16 {
+        match self {
+            MoveType::Regular => REGULAR_FLAG,
+            MoveType::PawnDoubleMove => PAWN_DOUBLE_MOVE_FLAG,
+            MoveType::EnPassant => EN_PASSANT_FLAG,
+            MoveType::CastlingKingSide => CASTLING_KING_SIDE_FLAG,
+            MoveType::CastlingQueenSide => CASTLING_QUEEN_SIDE_FLAG,
+            MoveType::PromotionQueen => PROMOTION_QUEEN_FLAG,
+            MoveType::PromotionKnight => PROMOTION_KNIGHT_FLAG,
+            MoveType::PromotionRook => PROMOTION_ROOK_FLAG,
+            MoveType::PromotionBishop => PROMOTION_BISHOP_FLAG,
+            MoveType::PromotionKing => PROMOTION_KING_FLAG,
+            MoveType::Drop(piece_type) => {
+                debug_assert!((piece::PAWN..=piece::QUEEN).contains(&piece_type), "Invalid drop piece");
+                DROP_FLAG + (piece_type - piece::PAWN) as u16
+            },
+        }
+    }
+
+    fn from_flags(flags: u16) -> Self {
+        match flags {
+            REGULAR_FLAG => MoveType::Regular,
+            PAWN_DOUBLE_MOVE_FLAG => MoveType::PawnDoubleMove,
+            EN_PASSANT_FLAG => MoveType::EnPassant,
+            CASTLING_KING_SIDE_FLAG => MoveType::CastlingKingSide,
+            CASTLING_QUEEN_SIDE_FLAG => MoveType::CastlingQueenSide,
+            PROMOTION_QUEEN_FLAG => MoveType::PromotionQueen,
+            PROMOTION_KNIGHT_FLAG => MoveType::PromotionKnight,
+            PROMOTION_ROOK_FLAG => MoveType::PromotionRook,
+            PROMOTION_BISHOP_FLAG => MoveType::PromotionBishop,
+            PROMOTION_KING_FLAG => MoveType::PromotionKing,
+            DROP_FLAG..=LAST_DROP_FLAG => MoveType::Drop((flags - DROP_FLAG) as usize + piece::PAWN),
+            _ => panic!("Invalid move flags {}", flags),
+        }
+    }
 }
 
+/// A move packed into 16 bits: the start square, the target square and 4 bits of flags for the move type
+///
+/// The drops have the same start and target square
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Move(u16);
+
 impl Move {
-    pub fn new(
-        start_square: usize, 
-        target_square: usize, 
-        capture_square: usize, 
-        move_type: MoveType
-    ) -> Self {
-        Self {
-            start_square,
-            target_square,
-            capture_square,
-            move_type
+    pub fn new(start_square: usize, target_square: usize, move_type: MoveType) -> Self {
+        debug_assert!(start_square < 64 && target_square < 64, "Square out of bounds");
+        Self(start_square as u16 | (target_square as u16) << TARGET_SQUARE_SHIFT | move_type.to_flags() << FLAGS_SHIFT)
+    }
+
+    /// Creates the move from its packed form, e.g. one stored in a transposition table
+    ///
+    /// The flags must be valid, use *try_from_packed* for the values that can be corrupt
+    pub fn from_packed(packed_move: u16) -> Self {
+        debug_assert!(packed_move >> FLAGS_SHIFT <= LAST_DROP_FLAG, "Invalid move flags");
+        Self(packed_move)
+    }
+
+    /// Same as *from_packed* but returns *None* if the flags don't belong to any move type
+    pub fn try_from_packed(packed_move: u16) -> Option<Self> {
+        (packed_move >> FLAGS_SHIFT <= LAST_DROP_FLAG).then_some(Self(packed_move))
+    }
+
+    pub fn get_packed(&self) -> u16 {
+        self.0
+    }
+
+    pub fn get_start_square(&self) -> usize {
+        (self.0 & SQUARE_MASK) as usize
+    }
+
+    pub fn get_target_square(&self) -> usize {
+        (self.0 >> TARGET_SQUARE_SHIFT & SQUARE_MASK) as usize
+    }
+
+    /// Returns the square of the captured piece (if there is one), which differs from the target square only for en passant
+    pub fn get_capture_square(&self) -> usize {
+        if self.is_en_passant() {
+            // the captured pawn stands on the start rank and the target file
+            (self.get_start_square() & !7) | (self.get_target_square() & 7)
+        } else {
+            self.get_target_square()
         }
     }
 
+    pub fn get_move_type(&self) -> MoveType {
+        MoveType::from_flags(self.get_flags())
+    }
+
+    fn get_flags(&self) -> u16 {
+        self.0 >> FLAGS_SHIFT
+    }
+
     pub fn is_promotion(&self) -> bool {
-        match self.move_type {
-            MoveType::PromotionQueen | MoveType::PromotionKnight | MoveType::PromotionRook | MoveType::PromotionBishop => true,
-            _ => false
-        }
+        (PROMOTION_QUEEN_FLAG..=PROMOTION_KING_FLAG).contains(&self.get_flags())
+    }
+
+    /// Returns the piece type the pawn promotes to, the move must be a promotion
+    pub fn get_promotion_piece_type(&self) -> usize {
+        debug_assert!(self.is_promotion(), "The move is not a promotion");
+        unsafe { *PROMOTION_PIECES.get_unchecked((self.get_flags() - PROMOTION_QUEEN_FLAG) as usize) }
+    }
+
+    pub fn is_pawn_double_move(&self) -> bool {
+        self.get_flags() == PAWN_DOUBLE_MOVE_FLAG
+    }
+
+    /// Returns *castling::KING_SIDE* or *castling::QUEEN_SIDE*, the move must be a castling
+    pub fn get_castling_side(&self) -> u8 {
+        debug_assert!(self.is_castling(), "The move is not a castling");
+        if self.get_flags() == CASTLING_KING_SIDE_FLAG {castling::KING_SIDE} else {castling::QUEEN_SIDE}
     }
 
     pub fn is_castling(&self) -> bool {
-        match self.move_type {
-            MoveType::CastlingKingSide | MoveType::CastlingQueenSide => true,
-            _ => false   
-        }
+        matches!(self.get_flags(), CASTLING_KING_SIDE_FLAG | CASTLING_QUEEN_SIDE_FLAG)
+    }
+
+    pub fn is_drop(&self) -> bool {
+        self.get_flags() >= DROP_FLAG
+    }
+
+    /// Returns the piece type taken from the pocket, the move must be a drop
+    pub fn get_drop_piece_type(&self) -> usize {
+        debug_assert!(self.is_drop(), "The move is not a drop");
+        (self.get_flags() - DROP_FLAG) as usize + piece::PAWN
+    }
+
+    pub fn is_en_passant(&self) -> bool {
+        self.get_flags() == EN_PASSANT_FLAG
     }
 
     pub fn from_uci(mov: UciMove, board: &Board) -> Self {
+        if let MoveType::Drop(_) = mov.move_type {
+            return Self::new(mov.target_square, mov.target_square, mov.move_type);
+        }
+
         let start_square = mov.start_square;
-        let target_square = mov.target_square;
+        let mut target_square = mov.target_square;
         let target_square_bb = bitboards::get_bit_from_square(target_square);
         let (color, piece_type) = board.get_piece_on_square(start_square);
         let is_capture = target_square_bb & board.get_all_occupied_squares() != 0;
-        let mut capture_square = target_square;
-
-        if is_capture && board.is_en_passant_possible() && target_square_bb == bitboards::get_bit_from_square(board.en_passant_capture_square()) {
-            capture_square = board.en_passant_pawn_square();
-        }
 
         let mut move_type = mov.move_type;
 
-        if piece_type == piece::KING && start_square == board::get_king_start_square(color) {
-            if target_square == board::get_king_side_square(color) {
-                move_type = MoveType::CastlingKingSide;
-            } else if target_square == board::get_queen_side_square(color) {
-                move_type = MoveType::CastlingQueenSide;
+        // the en passant target square is empty, the captured pawn stands next to the start square
+        if piece_type == piece::PAWN && !is_capture
+        && board.is_en_passant_possible() && target_square == board.en_passant_capture_square() {
+            move_type = MoveType::EnPassant;
+        }
+
+        if piece_type == piece::KING && board.get_variant().has_castling() {
+            if board.get_piece_on_square(target_square) == (color, piece::ROOK) {
+                // Chess960 castling is written as the king capturing its own rook
+                for (castling_side, castling_type) in [(castling::KING_SIDE, MoveType::CastlingKingSide), (castling::QUEEN_SIDE, MoveType::CastlingQueenSide)] {
+                    let castling_path = board.get_castling_path(color, castling_side);
+                    if board.get_castling_state(color) & castling_side != 0 && castling_path.rook_square == target_square {
+                        move_type = castling_type;
+                        target_square = castling_path.king_target_square;
+                    }
+                }
+            } else if !board.is_chess960() && start_square == board.get_king_start_square(color) {
+                if target_square == board::get_king_side_square(color) {
+                    move_type = MoveType::CastlingKingSide;
+                } else if target_square == board::get_queen_side_square(color) {
+                    move_type = MoveType::CastlingQueenSide;
+                }
             }
         }
 
@@ -131,27 +320,38 @@ impl Move {
             move_type = MoveType::PawnDoubleMove;
         }
 
-        Self {
-            start_square,
-            target_square,
-            capture_square,
-            move_type
+        Self::new(start_square, target_square, move_type)
+    }
+
+    /// Returns the move in UCI format, in Chess960 the castling is written as the king capturing its own rook
+    pub fn to_uci(&self, board: &Board) -> String {
+        if !board.is_chess960() || !self.is_castling() {
+            return self.to_string();
         }
+
+        let castling_side = if self.get_move_type() == MoveType::CastlingKingSide {castling::KING_SIDE} else {castling::QUEEN_SIDE};
+        let rook_square = board.get_castling_path(board.get_current_color(), castling_side).rook_square;
+        format!("{}{}", get_square_name(self.get_start_square()), get_square_name(rook_square))
     }
 }
 
 impl Display for Move {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let MoveType::Drop(piece_type) = self.get_move_type() {
+            return write!(f, "{}@{}", piece_to_fen_sym(piece::WHITE, piece_type), get_square_name(self.get_target_square()));
+        }
+
         write!(
             f, "{}{}{}", 
-            get_square_name(self.start_square), 
-            get_square_name(self.target_square), 
+            get_square_name(self.get_start_square()), 
+            get_square_name(self.get_target_square()), 
             if self.is_promotion() {
-                match self.move_type {
+                match self.get_move_type() {
                     MoveType::PromotionQueen => piece_to_fen_sym(piece::BLACK, piece::QUEEN).to_string(),
                     MoveType::PromotionKnight => piece_to_fen_sym(piece::BLACK, piece::KNIGHT).to_string(),
                     MoveType::PromotionRook => piece_to_fen_sym(piece::BLACK, piece::ROOK).to_string(),
                     MoveType::PromotionBishop => piece_to_fen_sym(piece::BLACK, piece::BISHOP).to_string(),
+                    MoveType::PromotionKing => piece_to_fen_sym(piece::BLACK, piece::KING).to_string(),
                     _ => String::new()
                 }
             } else {
@@ -159,4 +359,12 @@ impl Display for Move {
             }
         )
     }
-}
\ No newline at end of file
+}
+
+/// The move type is only known for promotions and drops, the moves that need the position
+/// to be told apart (castling, double pawn moves, en passant) are regular
+impl From<UciMove> for Move {
+    fn from(mov: UciMove) -> Self {
+        Move::new(mov.start_square, mov.target_square, mov.move_type)
+    }
+}
@@ -1,33 +1,34 @@
 use super::Move;
 
 /// Contains all information needed to undo a move
+///
+/// The squares and the piece types are stored as bytes to keep the record small, it is made on every move of the search
 pub struct MoveRecord {
     pub mov: Move,
-    pub captured_piece_type: usize,
+    pub captured_piece_type: u8,
     /// The previous en passant state
     pub is_en_passant_possible: bool,
-    pub en_passant_pawn_square: usize,
-    pub en_passant_capture_square: usize,
+    pub en_passant_pawn_square: u8,
+    pub en_passant_capture_square: u8,
     /// The previous castling states for both players
     pub old_castling_states: [u8; 2],
+    /// The previous halfmove clock, since it can't be recovered after being reset
+    pub old_halfmove_clock: usize,
+    /// The previous promoted pieces, needed to know whether a captured piece went to the pocket as a pawn
+    pub old_promoted_pieces_bb: u64,
+    /// Number of pieces the Atomic capture has blown up, they are on top of *Board::exploded_pieces*
+    pub exploded_piece_count: u8,
+    /// The previous numbers of checks given by each color in Three-check
+    pub old_check_counts: [u8; 2],
+    pub old_zobrist_key: u64,
 }
 
-impl MoveRecord {
-    pub fn new(
-        mov: Move,
-        captured_piece_type: usize,
-        is_en_passant_possible: bool,
-        en_passant_pawn_square: usize,
-        en_passant_capture_square: usize,
-        old_castling_states: [u8; 2],
-    ) -> Self {
-        Self {
-            mov,
-            captured_piece_type,
-            is_en_passant_possible,
-            en_passant_pawn_square,
-            en_passant_capture_square,
-            old_castling_states,
-        }
-    }
-}
\ No newline at end of file
+/// Contains all information needed to undo a null move
+pub struct NullMoveRecord {
+    /// The previous en passant state, which the null move clears
+    pub is_en_passant_possible: bool,
+    pub en_passant_pawn_square: usize,
+    pub en_passant_capture_square: usize,
+    pub old_halfmove_clock: usize,
+    pub old_zobrist_key: u64,
+}
@@ -0,0 +1,203 @@
+use crate::{board::Board, board_representation::{get_square_name, piece_to_fen_sym, try_get_square_from_name}, chess_parse_error::ChessParseError, move_generation::{move_gen, move_list::CrazyhouseMoveList}, piece};
+
+use super::{get_drop_piece_from_sym, get_promotion_piece, Move, MoveType};
+
+/// Returns the piece type corresponding to the uppercase SAN piece letter
+fn get_piece_from_san_sym(sym: char) -> Option<usize> {
+    match sym {
+        'K' => Some(piece::KING),
+        'N' => Some(piece::KNIGHT),
+        'B' => Some(piece::BISHOP),
+        'R' => Some(piece::ROOK),
+        'Q' => Some(piece::QUEEN),
+        _ => None,
+    }
+}
+
+fn get_promotion_move_type(piece_type: usize) -> Option<MoveType> {
+    match piece_type {
+        piece::QUEEN => Some(MoveType::PromotionQueen),
+        piece::KNIGHT => Some(MoveType::PromotionKnight),
+        piece::ROOK => Some(MoveType::PromotionRook),
+        piece::BISHOP => Some(MoveType::PromotionBishop),
+        piece::KING => Some(MoveType::PromotionKing),
+        _ => None,
+    }
+}
+
+impl Move {
+    /// Returns the move in Standard Algebraic Notation (e.g. *e4*, *Nbd7*, *exd8=Q+*, *O-O-O#*)
+    ///
+    /// The move must be legal in the provided position
+    pub fn to_san(&self, board: &Board) -> String {
+        let mut san = match self.get_move_type() {
+            MoveType::CastlingKingSide => String::from("O-O"),
+            MoveType::CastlingQueenSide => String::from("O-O-O"),
+            // the drops are written the same way as in UCI (*N@f7*)
+            MoveType::Drop(_) => self.to_string(),
+            _ => self.get_san_without_suffix(board),
+        };
+
+        let mut board_after_move = board.clone();
+        board_after_move.make_move(*self);
+        if board_after_move.get_variant_game_state().is_some() {
+            san.push('#'); // the game is won right away, like by exploding the king in Atomic
+        } else if move_gen::is_in_check(&board_after_move) {
+            let mut legal_moves = CrazyhouseMoveList::new();
+            move_gen::generate_moves(&mut legal_moves, &board_after_move);
+            san.push(if legal_moves.is_empty() {'#'} else {'+'});
+        }
+
+        san
+    }
+
+    fn get_san_without_suffix(&self, board: &Board) -> String {
+        let mut san = String::new();
+        let (_, piece_type) = board.get_piece_on_square(self.get_start_square());
+        let start_square_name = get_square_name(self.get_start_square());
+        let is_capture = board.get_piece_on_square(self.get_capture_square()).1 != piece::NONE;
+
+        if piece_type == piece::PAWN {
+            if is_capture {
+                san.push_str(&start_square_name[0..1]);
+                san.push('x');
+            }
+            san.push_str(get_square_name(self.get_target_square()));
+            if self.is_promotion() {
+                san.push('=');
+                san.push(piece_to_fen_sym(piece::WHITE, get_promotion_piece(self.get_move_type())));
+            }
+            return san;
+        }
+
+        san.push(piece_to_fen_sym(piece::WHITE, piece_type));
+
+        let mut legal_moves = CrazyhouseMoveList::new();
+        move_gen::generate_moves(&mut legal_moves, board);
+        let ambiguous_start_squares: Vec<&str> = legal_moves.iter()
+            .filter(|mov| mov.get_target_square() == self.get_target_square()
+                && mov.get_start_square() != self.get_start_square()
+                && board.get_piece_on_square(mov.get_start_square()).1 == piece_type)
+            .map(|mov| get_square_name(mov.get_start_square()))
+            .collect();
+
+        if !ambiguous_start_squares.is_empty() {
+            let is_file_shared = ambiguous_start_squares.iter().any(|name| name[0..1] == start_square_name[0..1]);
+            let is_rank_shared = ambiguous_start_squares.iter().any(|name| name[1..2] == start_square_name[1..2]);
+
+            if !is_file_shared {
+                san.push_str(&start_square_name[0..1]);
+            } else if !is_rank_shared {
+                san.push_str(&start_square_name[1..2]);
+            } else {
+                san.push_str(start_square_name);
+            }
+        }
+
+        if is_capture {
+            san.push('x');
+        }
+        san.push_str(get_square_name(self.get_target_square()));
+
+        san
+    }
+}
+
+impl Board {
+    /// Finds the legal move corresponding to the provided move in Standard Algebraic Notation
+    ///
+    /// Check, mate and annotation suffixes are ignored, both *O-O* and *0-0* are accepted for castling,
+    /// the pawn letter of the Crazyhouse drops can be omitted (*@e4*)
+    pub fn parse_san(&self, san: &str) -> Result<Move, ChessParseError> {
+        let invalid_san = || ChessParseError::InvalidSan(san.to_string());
+        let trimmed_san = san.trim()
+            .trim_end_matches("e.p.")
+            .trim_end()
+            .trim_end_matches(['+', '#', '!', '?']);
+
+        let mut legal_moves = CrazyhouseMoveList::new();
+        move_gen::generate_moves(&mut legal_moves, self);
+
+        let castling_type = match trimmed_san {
+            "O-O" | "0-0" => Some(MoveType::CastlingKingSide),
+            "O-O-O" | "0-0-0" => Some(MoveType::CastlingQueenSide),
+            _ => None,
+        };
+        if let Some(castling_type) = castling_type {
+            return legal_moves.iter().copied()
+                .find(|mov| mov.get_move_type() == castling_type)
+                .ok_or_else(|| ChessParseError::IllegalSanMove(san.to_string()));
+        }
+
+        if let Some((piece_sym, target_square_name)) = trimmed_san.split_once('@') {
+            let mut piece_syms = piece_sym.chars();
+            let piece_type = match (piece_syms.next(), piece_syms.next()) {
+                (None, _) => piece::PAWN,
+                (Some(sym), None) => get_drop_piece_from_sym(sym).map_err(|_| invalid_san())?,
+                _ => return Err(invalid_san()),
+            };
+            let target_square = try_get_square_from_name(target_square_name).map_err(|_| invalid_san())?;
+            return legal_moves.iter().copied()
+                .find(|mov| mov.get_move_type() == MoveType::Drop(piece_type) && mov.get_target_square() == target_square)
+                .ok_or_else(|| ChessParseError::IllegalSanMove(san.to_string()));
+        }
+
+        let mut syms: Vec<char> = trimmed_san.chars().collect();
+
+        let piece_type = match syms.first() {
+            Some(&sym) => get_piece_from_san_sym(sym).unwrap_or(piece::PAWN),
+            None => return Err(invalid_san()),
+        };
+        if piece_type != piece::PAWN {
+            syms.remove(0);
+        }
+
+        // the promotion can be written both as *e8=Q* and *e8Q*
+        let mut promotion_type = None;
+        if let Some(promotion_piece) = syms.last().and_then(|&sym| get_piece_from_san_sym(sym)) {
+            promotion_type = Some(get_promotion_move_type(promotion_piece).ok_or_else(invalid_san)?);
+            syms.pop();
+            if syms.last() == Some(&'=') {
+                syms.pop();
+            }
+        }
+
+        if syms.len() < 2 {
+            return Err(invalid_san());
+        }
+        let target_square_name: String = syms[syms.len() - 2..].iter().collect();
+        let target_square = try_get_square_from_name(&target_square_name).map_err(|_| invalid_san())?;
+
+        let mut start_file = None;
+        let mut start_rank = None;
+        for &sym in syms[..syms.len() - 2].iter().filter(|&&sym| sym != 'x' && sym != ':' && sym != '-') {
+            match sym {
+                'a'..='h' if start_file.is_none() => start_file = Some(sym),
+                '1'..='8' if start_rank.is_none() => start_rank = Some(sym),
+                _ => return Err(invalid_san()),
+            }
+        }
+
+        let mut matching_moves = legal_moves.iter().copied().filter(|mov| {
+            let start_square_name = get_square_name(mov.get_start_square());
+            let promotion_matches = if mov.is_promotion() {
+                promotion_type == Some(mov.get_move_type())
+            } else {
+                promotion_type.is_none()
+            };
+
+            mov.get_target_square() == target_square
+                && !mov.is_castling()
+                && promotion_matches
+                && self.get_piece_on_square(mov.get_start_square()).1 == piece_type
+                && start_file.is_none_or(|file| start_square_name.starts_with(file))
+                && start_rank.is_none_or(|rank| start_square_name.ends_with(rank))
+        });
+
+        match (matching_moves.next(), matching_moves.next()) {
+            (Some(mov), None) => Ok(mov),
+            (Some(_), Some(_)) => Err(ChessParseError::AmbiguousSanMove(san.to_string())),
+            (None, _) => Err(ChessParseError::IllegalSanMove(san.to_string())),
+        }
+    }
+}
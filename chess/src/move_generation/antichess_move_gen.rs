@@ -0,0 +1,49 @@
+/* Move generation of Antichess. The king is an ordinary piece there, so there are no checks, pins
+or castling to care about, but a capture has to be made whenever there is one */
+use crate::{bitboards, board::Board, r#move::{Move, MoveType}, piece};
+
+use super::{move_gen, move_list::MoveList, pseudo_legal_move_gen};
+
+/// Generates every legal move by the Antichess rules, which are only the captures if there are any
+pub fn generate_moves<const N: usize>(moves: &mut MoveList<N>, board: &Board) {
+    let first_move_index = moves.len();
+    pseudo_legal_move_gen::generate_moves(moves, board);
+
+    // the pawns can promote to a king as well
+    for index in first_move_index..moves.len() {
+        let mov = moves[index];
+        if mov.get_move_type() == MoveType::PromotionQueen {
+            move_gen::add_move(moves, Move::new(mov.get_start_square(), mov.get_target_square(), MoveType::PromotionKing));
+        }
+    }
+
+    let is_capture = |mov: &Move| board.get_piece_on_square(mov.get_capture_square()).1 != piece::NONE;
+    if !moves[first_move_index..].iter().any(is_capture) {
+        return;
+    }
+
+    let mut capture_count = first_move_index;
+    for index in first_move_index..moves.len() {
+        let mov = moves[index];
+        if is_capture(&mov) {
+            moves[capture_count] = mov;
+            capture_count += 1;
+        }
+    }
+    moves.truncate(capture_count);
+}
+
+/// Returns true if the side to move has a capture, which then has to be made
+pub fn has_capture(board: &Board) -> bool {
+    let current_color = board.get_current_color();
+    let all_opposite_occ = board.get_all_occupied_squares_for_color(1 - current_color);
+    let en_passant_capture_bb = if board.is_en_passant_possible() {
+        bitboards::get_bit_from_square(board.en_passant_capture_square())
+    } else {
+        0
+    };
+
+    board.get_attacks_bb(current_color, piece::PAWN) & (all_opposite_occ | en_passant_capture_bb) != 0
+        || [piece::KING, piece::KNIGHT, piece::BISHOP, piece::ROOK, piece::QUEEN].into_iter()
+            .any(|piece_type| board.get_attacks_bb(current_color, piece_type) & all_opposite_occ != 0)
+}
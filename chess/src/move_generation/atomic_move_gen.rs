@@ -0,0 +1,161 @@
+/* Move generation of Atomic chess. Every capture explodes the capturing piece together with
+every piece except pawns standing next to the capture square, so the pins and check masks
+of AttackCalculator don't apply here. The pseudo legal moves are checked one by one instead */
+use crate::{bitboards, board::Board, castling, r#move::{Move, MoveType}, piece, precomputed_data};
+
+use super::{move_gen, move_list::MoveList, pseudo_legal_move_gen};
+
+/// Returns the pieces that explode when a piece captures on the provided square,
+/// the capturing piece and the captured one are not included
+pub fn get_blast_bb(board: &Board, square: usize) -> u64 {
+    let pawns_bb = board.get_piece_bitboard(piece::WHITE, piece::PAWN) | board.get_piece_bitboard(piece::BLACK, piece::PAWN);
+    precomputed_data::SQUARE_DATA.get_bb_for_king(square) & board.get_all_occupied_squares() & !pawns_bb
+}
+
+/// Returns the pieces of the provided color except the king that attack the square, only the pieces
+/// inside of the provided mask are taken into account
+fn get_attackers_bb(board: &Board, square: usize, attacker_color: usize, all_occ: u64, attackers_mask: u64) -> u64 {
+    let queen_bb = board.get_piece_bitboard(attacker_color, piece::QUEEN);
+
+    // a pawn attacks the square if a pawn of the opposite color standing on it would attack the pawn
+    let attackers_bb = (precomputed_data::SQUARE_DATA.get_bb_for_pawn_attacks(1 - attacker_color, square)
+        & board.get_piece_bitboard(attacker_color, piece::PAWN))
+        | (precomputed_data::SQUARE_DATA.get_bb_for_knight(square) & board.get_piece_bitboard(attacker_color, piece::KNIGHT))
+        | (precomputed_data::ROOK_MAGIC_LOOKUP_TABLE.get_pseudo_legal_move_bb(square, all_occ)
+            & (board.get_piece_bitboard(attacker_color, piece::ROOK) | queen_bb))
+        | (precomputed_data::BISHOP_MAGIC_LOOKUP_TABLE.get_pseudo_legal_move_bb(square, all_occ)
+            & (board.get_piece_bitboard(attacker_color, piece::BISHOP) | queen_bb));
+
+    attackers_bb & attackers_mask
+}
+
+/// Returns true if the king of the provided color standing on the square could be blown up by the enemy
+///
+/// The kings can't capture, and a capture next to both kings would blow up the capturing side's king too,
+/// so a king touching the enemy king can't be attacked at all
+fn is_king_square_attacked(board: &Board, king_square: usize, color: usize, all_occ: u64, attackers_mask: u64) -> bool {
+    let opposite_king_bb = board.get_piece_bitboard(1 - color, piece::KING);
+    if precomputed_data::SQUARE_DATA.get_bb_for_king(king_square) & opposite_king_bb != 0 {
+        return false;
+    }
+
+    get_attackers_bb(board, king_square, 1 - color, all_occ, attackers_mask) != 0
+}
+
+/// Returns true if the king of the provided color is in check by the Atomic rules
+pub fn is_king_attacked(board: &Board, color: usize) -> bool {
+    let king_bb = board.get_piece_bitboard(color, piece::KING);
+    if king_bb == 0 {
+        return false;
+    }
+
+    is_king_square_attacked(board, bitboards::get_ls1b(king_bb), color, board.get_all_occupied_squares(), u64::MAX)
+}
+
+pub fn in_check(board: &Board) -> bool {
+    is_king_attacked(board, board.get_current_color())
+}
+
+/// Checks the pseudo legal move against the Atomic rules without making it
+pub fn is_legal(board: &Board, mov: Move, current_color: usize, king_square: usize) -> bool {
+    let opposite_color = 1 - current_color;
+    let all_occ = board.get_all_occupied_squares();
+    let start_bb = bitboards::get_bit_from_square(mov.get_start_square());
+    let target_bb = bitboards::get_bit_from_square(mov.get_target_square());
+    let is_capture = board.get_piece_on_square(mov.get_capture_square()).1 != piece::NONE;
+
+    if mov.get_start_square() == king_square {
+        // the king can't capture since it would explode itself
+        return !is_capture
+            && !is_king_square_attacked(board, mov.get_target_square(), current_color, (all_occ & !start_bb) | target_bb, u64::MAX);
+    }
+
+    if !is_capture {
+        return !is_king_square_attacked(board, king_square, current_color, (all_occ & !start_bb) | target_bb, u64::MAX);
+    }
+
+    let exploded_bb = start_bb
+        | bitboards::get_bit_from_square(mov.get_capture_square())
+        | get_blast_bb(board, mov.get_target_square());
+
+    if exploded_bb & bitboards::get_bit_from_square(king_square) != 0 {
+        return false; // blowing up both kings is not allowed either
+    }
+    if exploded_bb & board.get_piece_bitboard(opposite_color, piece::KING) != 0 {
+        return true; // the game is won right away, even if the own king is attacked
+    }
+
+    !is_king_square_attacked(board, king_square, current_color, all_occ & !exploded_bb, !exploded_bb)
+}
+
+fn generate_castling<const N: usize>(moves: &mut MoveList<N>, board: &Board, current_color: usize, king_square: usize) {
+    for (castling_side, castling_type) in [(castling::KING_SIDE, MoveType::CastlingKingSide), (castling::QUEEN_SIDE, MoveType::CastlingQueenSide)] {
+        if can_castle(board, current_color, king_square, castling_side) {
+            move_gen::add_move(
+                moves,
+                Move::new(
+                    king_square,
+                    board.get_castling_path(current_color, castling_side).king_target_square,
+                    castling_type,
+                )
+            );
+        }
+    }
+}
+
+/// Returns true if the king can castle to the provided side (*castling::KING_SIDE* or *castling::QUEEN_SIDE*)
+/// by the Atomic rules, the squares next to the enemy king are safe for it
+pub fn can_castle(board: &Board, current_color: usize, king_square: usize, castling_side: u8) -> bool {
+    let all_occ = board.get_all_occupied_squares();
+    let king_bb = bitboards::get_bit_from_square(king_square);
+
+    if board.get_castling_state(current_color) & castling_side == 0
+    || is_king_square_attacked(board, king_square, current_color, all_occ, u64::MAX) {
+        return false;
+    }
+
+    let castling_path = board.get_castling_path(current_color, castling_side);
+    if castling_path.empty_mask & all_occ != 0 {
+        return false;
+    }
+
+    let mut king_path_bb = castling_path.king_path_mask;
+    while king_path_bb != 0 {
+        let path_square = bitboards::get_ls1b(king_path_bb);
+        king_path_bb &= king_path_bb - 1;
+        if is_king_square_attacked(board, path_square, current_color, all_occ & !king_bb, u64::MAX) {
+            return false;
+        }
+    }
+
+    // in Chess960 the castling rook can be the one shielding the king's target square
+    let occupancy_after_castling = (all_occ & !king_bb & !bitboards::get_bit_from_square(castling_path.rook_square))
+        | bitboards::get_bit_from_square(castling_path.king_target_square)
+        | bitboards::get_bit_from_square(castling_path.rook_target_square);
+    !is_king_square_attacked(board, castling_path.king_target_square, current_color, occupancy_after_castling, u64::MAX)
+}
+
+/// Generates every legal move by the Atomic rules, there are none once a king has exploded
+pub fn generate_moves<const N: usize>(moves: &mut MoveList<N>, board: &Board) {
+    let current_color = board.get_current_color();
+    let king_bb = board.get_piece_bitboard(current_color, piece::KING);
+    if king_bb == 0 || board.get_piece_bitboard(1 - current_color, piece::KING) == 0 {
+        return;
+    }
+    let king_square = bitboards::get_ls1b(king_bb);
+
+    let first_move_index = moves.len();
+    pseudo_legal_move_gen::generate_moves(moves, board);
+
+    let mut legal_move_count = first_move_index;
+    for index in first_move_index..moves.len() {
+        let mov = moves[index];
+        if is_legal(board, mov, current_color, king_square) {
+            moves[legal_move_count] = mov;
+            legal_move_count += 1;
+        }
+    }
+    moves.truncate(legal_move_count);
+
+    generate_castling(moves, board, current_color, king_square);
+}
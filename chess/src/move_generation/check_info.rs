@@ -0,0 +1,213 @@
+/* Tells whether a move gives check before it is made. Everything that depends only on the position
+(the checking squares of every piece type and the discovered check candidates) is computed once,
+so many moves of the same position can be checked cheaply */
+use crate::{bitboards, board::Board, castling::{self, CastlingPath}, r#move::{get_promotion_piece, Move, MoveType}, piece, precomputed_data, variant::Variant};
+
+use super::move_gen;
+
+/// Returns the squares a piece of the provided type and color gives check from to the king
+/// standing on the provided square
+pub fn get_check_squares_bb(piece_type: usize, color: usize, king_square: usize, all_occ: u64) -> u64 {
+    match piece_type {
+        // a pawn checks the king from the squares a pawn of the king's color attacks from the king square
+        piece::PAWN => precomputed_data::SQUARE_DATA.get_bb_for_pawn_attacks(1 - color, king_square),
+        piece::KNIGHT => precomputed_data::SQUARE_DATA.get_bb_for_knight(king_square),
+        piece::BISHOP => precomputed_data::BISHOP_MAGIC_LOOKUP_TABLE.get_pseudo_legal_move_bb(king_square, all_occ),
+        piece::ROOK => precomputed_data::ROOK_MAGIC_LOOKUP_TABLE.get_pseudo_legal_move_bb(king_square, all_occ),
+        piece::QUEEN => precomputed_data::BISHOP_MAGIC_LOOKUP_TABLE.get_pseudo_legal_move_bb(king_square, all_occ)
+            | precomputed_data::ROOK_MAGIC_LOOKUP_TABLE.get_pseudo_legal_move_bb(king_square, all_occ),
+        _ => 0,
+    }
+}
+
+/// Returns the pieces of the provided color that stand between their own slider and the enemy king
+/// standing on the provided square, moving such a piece off the line gives a discovered check
+///
+/// It is the same as the pins computed by *AttackCalculator*, only from the other side's point of view
+pub fn get_discovered_check_candidates_bb(board: &Board, color: usize, king_square: usize, all_occ: u64) -> u64 {
+    let own_occ = board.get_all_occupied_squares_for_color(color);
+    let queen_bb = board.get_piece_bitboard(color, piece::QUEEN);
+    let mut candidates_bb = 0;
+
+    for (magic_lookup_table, slider_bb) in [
+        (&precomputed_data::ROOK_MAGIC_LOOKUP_TABLE, board.get_piece_bitboard(color, piece::ROOK) | queen_bb),
+        (&precomputed_data::BISHOP_MAGIC_LOOKUP_TABLE, board.get_piece_bitboard(color, piece::BISHOP) | queen_bb),
+    ] {
+        if slider_bb == 0 {
+            continue;
+        }
+
+        let king_rays_bb = magic_lookup_table.get_pseudo_legal_move_bb(king_square, all_occ);
+        let mut blockers_bb = king_rays_bb & own_occ;
+        while blockers_bb != 0 {
+            let blocker_bb = bitboards::get_bit_from_square(bitboards::get_ls1b(blockers_bb));
+            blockers_bb &= blockers_bb - 1;
+
+            // the sliders seen through the blocker
+            let x_ray_bb = magic_lookup_table.get_pseudo_legal_move_bb(king_square, all_occ & !blocker_bb) & !king_rays_bb;
+            if x_ray_bb & slider_bb != 0 {
+                candidates_bb |= blocker_bb;
+            }
+        }
+    }
+
+    candidates_bb
+}
+
+/// Returns the whole rank, file or diagonal going through both squares, zero if they are not on one line
+fn get_line_through_bb(first_square: usize, second_square: usize) -> u64 {
+    let second_square_bb = bitboards::get_bit_from_square(second_square);
+    for direction_index in 0..4 {
+        let line_bb = precomputed_data::SQUARE_DATA.get_file_rank_diagonal_mask(first_square, direction_index);
+        if line_bb & second_square_bb != 0 {
+            return line_bb;
+        }
+    }
+    0
+}
+
+/// Returns true if any slider of the provided color attacks the king square with the provided occupancy,
+/// the rooks are passed separately since the castling moves one of them
+fn is_king_attacked_by_sliders(board: &Board, color: usize, king_square: usize, occupancy: u64, rook_bb: u64) -> bool {
+    let queen_bb = board.get_piece_bitboard(color, piece::QUEEN);
+
+    precomputed_data::ROOK_MAGIC_LOOKUP_TABLE.get_pseudo_legal_move_bb(king_square, occupancy) & (rook_bb | queen_bb) != 0
+    || precomputed_data::BISHOP_MAGIC_LOOKUP_TABLE.get_pseudo_legal_move_bb(king_square, occupancy)
+        & (board.get_piece_bitboard(color, piece::BISHOP) | queen_bb) != 0
+}
+
+/// Checking squares and discovered check candidates of the side to move against the enemy king
+///
+/// Only valid for the position it was created for and for the variants with the regular check rules
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CheckInfo {
+    /// The squares each piece type gives check from, indexed by the piece type
+    check_squares_bbs: [u64; 7],
+    discovered_check_candidates_bb: u64,
+    opposite_king_square: usize,
+}
+
+impl CheckInfo {
+    pub fn new(board: &Board) -> Self {
+        let current_color = board.get_current_color();
+        let all_occ = board.get_all_occupied_squares();
+        let opposite_king_square = bitboards::get_ls1b(board.get_piece_bitboard(1 - current_color, piece::KING));
+
+        let mut check_squares_bbs = [0; 7];
+        for piece_type in [piece::PAWN, piece::KNIGHT, piece::BISHOP, piece::ROOK, piece::QUEEN] {
+            check_squares_bbs[piece_type] = get_check_squares_bb(piece_type, current_color, opposite_king_square, all_occ);
+        }
+
+        Self {
+            check_squares_bbs,
+            discovered_check_candidates_bb: get_discovered_check_candidates_bb(board, current_color, opposite_king_square, all_occ),
+            opposite_king_square,
+        }
+    }
+
+    pub fn get_check_squares_bb(&self, piece_type: usize) -> u64 {
+        debug_assert!(piece_type < 7, "Piece type out of bounds");
+        unsafe { *self.check_squares_bbs.get_unchecked(piece_type) }
+    }
+
+    pub fn get_discovered_check_candidates_bb(&self) -> u64 {
+        self.discovered_check_candidates_bb
+    }
+
+    pub fn get_opposite_king_square(&self) -> usize {
+        self.opposite_king_square
+    }
+
+    /// Returns the target squares the piece gives check from when moving from the start square,
+    /// directly or by revealing a slider behind it
+    pub fn get_check_targets_bb(&self, piece_type: usize, start_square: usize) -> u64 {
+        let mut check_targets_bb = self.get_check_squares_bb(piece_type);
+        if self.discovered_check_candidates_bb & bitboards::get_bit_from_square(start_square) != 0 {
+            // leaving the line to the enemy king reveals the check
+            check_targets_bb |= !get_line_through_bb(start_square, self.opposite_king_square);
+        }
+        check_targets_bb
+    }
+
+    /// Returns true if the castling gives check, either with the rook or by the king leaving the line
+    /// between another slider and the enemy king
+    pub fn is_castling_check(&self, board: &Board, king_square: usize, castling_path: &CastlingPath) -> bool {
+        let current_color = board.get_current_color();
+        let occupancy_after_castling = (board.get_all_occupied_squares()
+            & !bitboards::get_bit_from_square(king_square)
+            & !bitboards::get_bit_from_square(castling_path.rook_square))
+            | bitboards::get_bit_from_square(castling_path.king_target_square)
+            | bitboards::get_bit_from_square(castling_path.rook_target_square);
+        let rooks_after_castling_bb = (board.get_piece_bitboard(current_color, piece::ROOK)
+            & !bitboards::get_bit_from_square(castling_path.rook_square))
+            | bitboards::get_bit_from_square(castling_path.rook_target_square);
+
+        is_king_attacked_by_sliders(board, current_color, self.opposite_king_square, occupancy_after_castling, rooks_after_castling_bb)
+    }
+
+    /// Returns true if moving the piece from the start square to the target square uncovers a slider
+    fn is_discovered_check(&self, start_square: usize, target_square_bb: u64) -> bool {
+        self.discovered_check_candidates_bb & bitboards::get_bit_from_square(start_square) != 0
+        && get_line_through_bb(start_square, self.opposite_king_square) & target_square_bb == 0
+    }
+
+    /// Returns true if the legal move gives check, the board must be the one this info was created for
+    pub fn gives_check(&self, board: &Board, mov: &Move) -> bool {
+        let current_color = board.get_current_color();
+        let start_square = mov.get_start_square();
+        let start_square_bb = bitboards::get_bit_from_square(start_square);
+        let target_square_bb = bitboards::get_bit_from_square(mov.get_target_square());
+        let move_type = mov.get_move_type();
+
+        match move_type {
+            MoveType::Drop(piece_type) => return self.get_check_squares_bb(piece_type) & target_square_bb != 0,
+            MoveType::CastlingKingSide | MoveType::CastlingQueenSide => {
+                let castling_side = if move_type == MoveType::CastlingKingSide {castling::KING_SIDE} else {castling::QUEEN_SIDE};
+                return self.is_castling_check(board, start_square, board.get_castling_path(current_color, castling_side));
+            },
+            _ => (),
+        }
+
+        if self.is_discovered_check(start_square, target_square_bb) {
+            return true;
+        }
+
+        match move_type {
+            MoveType::EnPassant => {
+                if self.get_check_squares_bb(piece::PAWN) & target_square_bb != 0 {
+                    return true;
+                }
+                // the captured pawn can uncover a slider as well
+                let occupancy_after_move = (board.get_all_occupied_squares()
+                    & !start_square_bb
+                    & !bitboards::get_bit_from_square(mov.get_capture_square()))
+                    | target_square_bb;
+                is_king_attacked_by_sliders(board, current_color, self.opposite_king_square, occupancy_after_move, board.get_piece_bitboard(current_color, piece::ROOK))
+            },
+            _ if mov.is_promotion() => {
+                // the promoted piece attacks through the square the pawn has left
+                let occupancy_after_move = (board.get_all_occupied_squares() & !start_square_bb) | target_square_bb;
+                get_check_squares_bb(get_promotion_piece(move_type), current_color, self.opposite_king_square, occupancy_after_move) & target_square_bb != 0
+            },
+            _ => self.get_check_squares_bb(board.get_piece_on_square(start_square).1) & target_square_bb != 0,
+        }
+    }
+}
+
+impl Board {
+    /// Returns true if the legal move gives check, following the rules of the variant
+    ///
+    /// Use *CheckInfo* directly to check many moves of the same position
+    pub fn gives_check(&self, mov: &Move) -> bool {
+        match self.get_variant() {
+            Variant::Antichess => false,
+            Variant::Atomic => {
+                // the explosions can remove the checking pieces as well as the blocking ones
+                let mut board_after_move = self.clone();
+                board_after_move.make_move(*mov);
+                move_gen::is_in_check(&board_after_move)
+            },
+            _ => CheckInfo::new(self).gives_check(self, mov),
+        }
+    }
+}
@@ -1,2 +1,7 @@
 pub mod attack_calculator;
-pub mod move_gen;
\ No newline at end of file
+pub mod check_info;
+pub mod move_gen;
+pub mod move_list;
+pub mod pseudo_legal_move_gen;
+pub mod atomic_move_gen;
+pub mod antichess_move_gen;
\ No newline at end of file
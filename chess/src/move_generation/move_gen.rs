@@ -1,49 +1,34 @@
 use once_cell::sync::Lazy;
 
-use crate::{bitboards, board::{self, Board}, castling, r#move::{Move, MoveType}, piece, precomputed_data::{self, magic_lookup_table::MagicLookupTable}};
+use crate::{bitboards, board::{self, Board}, castling::{self, CastlingPath}, r#move::{Move, MoveType}, piece, precomputed_data::{self, magic_lookup_table::MagicLookupTable}, variant::Variant};
 
-use super::attack_calculator::AttackCalculator;
+use super::{antichess_move_gen, atomic_move_gen, attack_calculator::AttackCalculator, check_info::CheckInfo, move_list::MoveList};
 
 // constants
-/// Represents the theoretical maximum number of 
-/// available moves in a single chess position. 
-/// The number was found on the internet
-pub const MAX_MOVES_PER_POS: usize = 150;
-
-const KING_SIDE_CASTLING_MASK: [u64; 2] = [bitboards::WHITE_KING_SIDE_CASTLING_MASK, bitboards::BLACK_KING_SIDE_CASTLING_MASK];
-fn get_king_side_castling_mask(color: usize) -> u64 {
-    debug_assert!(color < 2, "Color index out of bounds");
-    unsafe { *KING_SIDE_CASTLING_MASK.get_unchecked(color) }
-}
-const QUEEN_SIDE_CASTLING_MASK: [u64; 2] = [bitboards::WHITE_QUEEN_SIDE_CASTLING_MASK, bitboards::BLACK_QUEEN_SIDE_CASTLING_MASK];
-fn get_queen_side_castling_mask(color: usize) -> u64 {
-    debug_assert!(color < 2, "Color index out of bounds");
-    unsafe { *QUEEN_SIDE_CASTLING_MASK.get_unchecked(color) }
-}
-const QUEEN_SIDE_CASTLING_PATH_MASK: [u64; 2] = [bitboards::WHITE_QUEEN_SIDE_CASTLING_KING_PATH_MASK, bitboards::BLACK_QUEEN_SIDE_CASTLING_KING_PATH_MASK];
-fn get_queen_side_castling_path_mask(color: usize) -> u64 {
-    debug_assert!(color < 2, "Color index out of bounds");
-    unsafe { *QUEEN_SIDE_CASTLING_PATH_MASK.get_unchecked(color) }
-}
+/// Capacity of *MoveList*. The maximum number of legal moves
+/// in a chess position is 218
+pub const MAX_MOVES_PER_POS: usize = 256;
+/// Capacity of *CrazyhouseMoveList*, every pocket piece type
+/// can be dropped to every square on top of the board moves
+pub const MAX_CRAZYHOUSE_MOVES_PER_POS: usize = MAX_MOVES_PER_POS + board::POCKET_PIECE_TYPES.len() * 64;
 
 const PAWN_SHIFT_AMOUNT: [i8; 2] = [8, -8];
-fn get_pawn_shift_amount(color: usize) -> i8 {
+pub(crate) fn get_pawn_shift_amount(color: usize) -> i8 {
     debug_assert!(color < 2, "Color index out of bounds");
     unsafe { *PAWN_SHIFT_AMOUNT.get_unchecked(color) }
 }
 const PAWN_PROMOTION_RANK: [u64; 2] = [bitboards::EIGHTH_RANK_MASK, bitboards::FIRST_RANK_MASK];
-fn get_pawn_promotion_rank(color: usize) -> u64 {
+pub(crate) fn get_pawn_promotion_rank(color: usize) -> u64 {
     debug_assert!(color < 2, "Color index out of bounds");
     unsafe { *PAWN_PROMOTION_RANK.get_unchecked(color) }
 }
 const PAWN_START_RANK: [u64; 2] = [bitboards::SECOND_RANK_MASK, bitboards::SEVENTH_RANK_MASK];
-fn get_pawn_start_rank(color: usize) -> u64 {
+pub(crate) fn get_pawn_start_rank(color: usize) -> u64 {
     debug_assert!(color < 2, "Color index out of bounds");
     unsafe { *PAWN_START_RANK.get_unchecked(color) }
 }
 
-fn add_move(moves: &mut Vec<Move>, move_: Move) {
-    debug_assert!(moves.len() < MAX_MOVES_PER_POS, "Moves vector is full");
+pub(super) fn add_move<const N: usize>(moves: &mut MoveList<N>, move_: Move) {
     moves.push(move_);
 }
 
@@ -55,18 +40,16 @@ pub fn print(moves: &[Move]) {
     println!();
 }
 
-fn add_promotion_moves(
-    moves: &mut Vec<Move>,
+pub(super) fn add_promotion_moves<const N: usize>(
+    moves: &mut MoveList<N>,
     start_square: usize, 
     target_square: usize, 
-    capture_square: usize,
 ) {
     add_move(
         moves,
         Move::new(
             start_square, 
             target_square, 
-            capture_square, 
             MoveType::PromotionQueen,
         )
     );
@@ -76,7 +59,6 @@ fn add_promotion_moves(
         Move::new(
             start_square, 
             target_square, 
-            capture_square, 
             MoveType::PromotionKnight,
         )
     );
@@ -86,7 +68,6 @@ fn add_promotion_moves(
         Move::new(
             start_square, 
             target_square, 
-            capture_square, 
             MoveType::PromotionRook,
         )
     );
@@ -96,53 +77,238 @@ fn add_promotion_moves(
         Move::new(
             start_square, 
             target_square, 
-            capture_square, 
             MoveType::PromotionBishop,
         )
     );
 }
 
-pub fn generate_moves(moves: &mut Vec<Move>, board: &mut Board) {
+/// Returns true if the side to move is in check, following the rules of the variant
+pub fn is_in_check(board: &Board) -> bool {
+    match board.get_variant() {
+        Variant::Atomic => atomic_move_gen::in_check(board),
+        Variant::Antichess => false,
+        _ => AttackCalculator::new(board).in_check(),
+    }
+}
+
+/// Part of the legal moves to generate, so the search can try the most promising moves first
+/// and skip generating the rest after a cutoff
+///
+/// *Captures* and *Quiets* together give every legal move
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GenType {
+    All,
+    /// Captures (en passant included) and promotions
+    Captures,
+    /// Every move that is neither a capture nor a promotion, castling and drops included
+    Quiets,
+    /// Every legal move if the side to move is in check, otherwise none
+    Evasions,
+    /// The moves of *Quiets* that give check
+    QuietChecks,
+}
+
+/// Restricts the target squares of the pieces to the moves of a single *GenType*
+struct StageMasks {
+    /// Squares the pieces except pawns can move to
+    target_bb: u64,
+    include_captures: bool,
+    include_quiets: bool,
+    /// Only set for *GenType::QuietChecks*, the moves then have to give check
+    check_info: Option<CheckInfo>,
+}
+
+impl StageMasks {
+    fn new(board: &Board, gen_type: GenType, current_color: usize, all_occ: u64, not_all_current_occ: u64) -> Self {
+        let all_opposite_occ = board.get_all_occupied_squares_for_color(1 - current_color);
+        let (include_captures, include_quiets) = match gen_type {
+            GenType::All | GenType::Evasions => (true, true),
+            GenType::Captures => (true, false),
+            GenType::Quiets | GenType::QuietChecks => (false, true),
+        };
+
+        let mut target_bb = 0;
+        if include_captures {
+            target_bb |= all_opposite_occ;
+        }
+        if include_quiets {
+            target_bb |= !all_occ;
+        }
+
+        Self {
+            target_bb: target_bb & not_all_current_occ,
+            include_captures,
+            include_quiets,
+            check_info: (gen_type == GenType::QuietChecks).then(|| CheckInfo::new(board)),
+        }
+    }
+
+    /// Returns the squares the piece gives check from when moving from the start square,
+    /// every square if the stage doesn't need checks
+    fn get_check_targets_bb(&self, piece_type: usize, start_square: usize) -> u64 {
+        match &self.check_info {
+            Some(check_info) => check_info.get_check_targets_bb(piece_type, start_square),
+            None => u64::MAX,
+        }
+    }
+}
+
+/// Position data shared by the move generation of every piece in the same stage
+struct GenContext<'a> {
+    stage: &'a StageMasks,
+    /// Gives the attacked squares, the squares blocking a single check, the pins and the forbidden en passant square
+    attack_calculator: &'a AttackCalculator,
+    current_color: usize,
+    king_square: usize,
+    all_occ: u64,
+    all_opposite_occ: u64,
+}
+
+impl GenContext<'_> {
+    /// Returns true if the king is in a single check, the moves then have to block it or capture the checking piece
+    fn is_single_check(&self) -> bool {
+        self.attack_calculator.check_block_bb != 0
+    }
+}
+
+/// Generates every legal move of the side to move
+pub fn generate_moves<const N: usize>(moves: &mut MoveList<N>, board: &Board) {
+    generate_staged_moves(moves, board, GenType::All);
+}
+
+/// Generates only the legal moves belonging to the provided stage
+pub fn generate_staged_moves<const N: usize>(moves: &mut MoveList<N>, board: &Board, gen_type: GenType) {
+    debug_assert!(N >= MAX_CRAZYHOUSE_MOVES_PER_POS || !board.get_variant().has_pockets(), "Move list is too short for the drops");
+    // there are no moves once the game is won by a rule of the variant, like the third check
+    if board.get_variant_game_state().is_some() {
+        return;
+    }
+
+    match board.get_variant() {
+        Variant::Atomic | Variant::Antichess => {
+            generate_variant_staged_moves(moves, board, gen_type);
+            return;
+        },
+        _ => (),
+    }
+
     let attack_calculator = AttackCalculator::new(board);
+    if gen_type == GenType::Evasions && !attack_calculator.in_check() {
+        return;
+    }
+
     let current_color = board.get_current_color();
-    let king_square = board.get_king_square(current_color);
-    let attacked_squares_bb = attack_calculator.squares_in_attack_bb; 
     let all_occ = board.get_all_occupied_squares();
     let not_all_current_occ = !board.get_all_occupied_squares_for_color(current_color);
+    let stage = StageMasks::new(board, gen_type, current_color, all_occ, not_all_current_occ);
+    let context = GenContext {
+        stage: &stage,
+        attack_calculator: &attack_calculator,
+        current_color,
+        king_square: board.get_king_square(current_color),
+        all_occ,
+        all_opposite_occ: board.get_all_occupied_squares_for_color(1 - current_color),
+    };
 
-    generate_king(moves, board, current_color, king_square, attacked_squares_bb, all_occ, not_all_current_occ, attack_calculator.in_check());
+    generate_king(moves, board, &context);
     if attack_calculator.is_in_double_check {
         return; // No other moves are possible
     }
 
-    let check_block_bb = attack_calculator.check_block_bb;
-    let is_single_check = check_block_bb != 0;
-    let opposite_color = board.get_opposite_color();
-    let all_opposite_occ = board.get_all_occupied_squares_for_color(opposite_color);
-    let pin_bbs = attack_calculator.pins_bbs;
+    generate_pawns(moves, board, &context);
+    generate_knights(moves, board, &context);
 
-    generate_pawns(moves, board, &attack_calculator, current_color, all_occ, all_opposite_occ, check_block_bb, &pin_bbs, is_single_check);
+    // the first two pins are along the files and the ranks, the last two along the diagonals
+    generate_sliders(moves, board, &context, piece::ROOK, [0, 1], [2, 3], &precomputed_data::ROOK_MAGIC_LOOKUP_TABLE);
+    generate_sliders(moves, board, &context, piece::BISHOP, [2, 3], [0, 1], &precomputed_data::BISHOP_MAGIC_LOOKUP_TABLE);
+    generate_queens(moves, board, &context);
 
-    let pin_bb_1 = unsafe { *pin_bbs.get_unchecked(0) };
-    let pin_bb_2 = unsafe { *pin_bbs.get_unchecked(1) };
-    let pin_bb_3 = unsafe { *pin_bbs.get_unchecked(2) };
-    let pin_bb_4 = unsafe { *pin_bbs.get_unchecked(3) };
+    if stage.include_quiets && board.get_variant().has_pockets() {
+        generate_drops(moves, board, &context);
+    }
+}
 
-    let first_second_pins = pin_bb_1 | pin_bb_2;
-    let third_fourth_pins = pin_bb_3 | pin_bb_4;
+/// Atomic and Antichess generate their moves with their own legality rules,
+/// so the moves of the stage are picked out of all of them
+fn generate_variant_staged_moves<const N: usize>(moves: &mut MoveList<N>, board: &Board, gen_type: GenType) {
+    if gen_type == GenType::Evasions && !is_in_check(board) {
+        return;
+    }
 
-    generate_knights(moves, board, current_color, not_all_current_occ, check_block_bb, first_second_pins | third_fourth_pins);
+    let first_move_index = moves.len();
+    match board.get_variant() {
+        Variant::Atomic => atomic_move_gen::generate_moves(moves, board),
+        Variant::Antichess => antichess_move_gen::generate_moves(moves, board),
+        _ => unreachable!("Only Atomic and Antichess have their own move generation"),
+    }
+    if gen_type == GenType::All || gen_type == GenType::Evasions {
+        return;
+    }
 
-    generate_sliders(moves, board, piece::ROOK, third_fourth_pins, pin_bb_1, pin_bb_2, 0, 1, &precomputed_data::ROOK_MAGIC_LOOKUP_TABLE, current_color, is_single_check, check_block_bb, all_occ, not_all_current_occ);
-    generate_sliders(moves, board, piece::BISHOP, first_second_pins, pin_bb_3, pin_bb_4, 2, 3, &precomputed_data::BISHOP_MAGIC_LOOKUP_TABLE, current_color, is_single_check, check_block_bb, all_occ, not_all_current_occ);
-    generate_queens(moves, board, current_color, all_occ, not_all_current_occ, is_single_check, check_block_bb, &pin_bbs);
+    let is_capture_or_promotion = |mov: Move| mov.is_promotion()
+        || board.get_piece_on_square(mov.get_capture_square()).1 != piece::NONE;
+    let mut board_after_move = board.clone();
+
+    let mut stage_move_count = first_move_index;
+    for index in first_move_index..moves.len() {
+        let mov = moves[index];
+        let is_stage_move = match gen_type {
+            GenType::Captures => is_capture_or_promotion(mov),
+            GenType::Quiets => !is_capture_or_promotion(mov),
+            _ => !is_capture_or_promotion(mov) && {
+                let move_record = board_after_move.make_move(mov);
+                let gives_check = is_in_check(&board_after_move);
+                board_after_move.undo_move(move_record);
+                gives_check
+            },
+        };
+        if is_stage_move {
+            moves[stage_move_count] = mov;
+            stage_move_count += 1;
+        }
+    }
+    moves.truncate(stage_move_count);
 }
 
-fn generate_king(moves: &mut Vec<Move>, board: &mut Board, current_color: usize, king_square: usize, attacked_squares_bb: u64, all_occ: u64, not_all_current_occ: u64, is_check: bool) {
+/// Generates the Crazyhouse drops, which can go to any empty square (pawns can't go to the back ranks)
+/// or only between the king and the checking piece when in check
+fn generate_drops<const N: usize>(moves: &mut MoveList<N>, board: &Board, context: &GenContext) {
+    let GenContext { stage, current_color, .. } = *context;
+    let mut drop_squares_bb = !context.all_occ;
+    if context.is_single_check() {
+        drop_squares_bb &= context.attack_calculator.check_block_bb;
+    }
+
+    for piece_type in board::POCKET_PIECE_TYPES {
+        if board.get_pocket_count(current_color, piece_type) == 0 {
+            continue;
+        }
+
+        let mut piece_drop_squares_bb = drop_squares_bb;
+        if piece_type == piece::PAWN {
+            piece_drop_squares_bb &= !(bitboards::FIRST_RANK_MASK | bitboards::EIGHTH_RANK_MASK);
+        }
+        if let Some(check_info) = &stage.check_info {
+            piece_drop_squares_bb &= check_info.get_check_squares_bb(piece_type);
+        }
+
+        while piece_drop_squares_bb != 0 {
+            let target_square = bitboards::get_ls1b(piece_drop_squares_bb);
+            piece_drop_squares_bb &= piece_drop_squares_bb - 1;
+
+            add_move(moves, Move::new(target_square, target_square, MoveType::Drop(piece_type)));
+        }
+    }
+}
+
+fn generate_king<const N: usize>(moves: &mut MoveList<N>, board: &Board, context: &GenContext) {
+    let GenContext { stage, current_color, king_square, all_occ, .. } = *context;
+    let attacked_squares_bb = context.attack_calculator.squares_in_attack_bb;
+    let is_check = context.attack_calculator.in_check();
     let mut pseudo_moves_bb = precomputed_data::SQUARE_DATA.get_bb_for_king(king_square) 
     & !attacked_squares_bb // The king cannot move to a square that is attacked
-    & not_all_current_occ; 
-    // The king cannot move to a square that is occupied by a friendly piece
+    & stage.target_bb // The king cannot move to a square that is occupied by a friendly piece
+    & stage.get_check_targets_bb(piece::KING, king_square);
 
     while pseudo_moves_bb != 0 {
         let target_square = bitboards::get_ls1b(pseudo_moves_bb);
@@ -153,58 +319,72 @@ fn generate_king(moves: &mut Vec<Move>, board: &mut Board, current_color: usize,
             Move::new(
                 king_square, 
                 target_square, 
-                target_square, 
                 MoveType::Regular,
             )
         );
     }
 
-    if is_check {
+    if is_check || !stage.include_quiets {
         return; // The king cannot castle if it is in check
     }
 
-    let castling_blocker_bb = all_occ | attacked_squares_bb;
-    let castling_state = board.get_castling_state(current_color);
-
-    if castling::can_king_side(*castling_state) {
-        let king_side_castling_mask = get_king_side_castling_mask(current_color);
+    for (castling_side, castling_type) in [(castling::KING_SIDE, MoveType::CastlingKingSide), (castling::QUEEN_SIDE, MoveType::CastlingQueenSide)] {
+        if !can_castle(board, current_color, king_square, castling_side, attacked_squares_bb, all_occ) {
+            continue;
+        }
 
-        if (king_side_castling_mask & castling_blocker_bb) == 0 {
-            let king_side_castling_square = board::get_king_side_square(current_color);
-            add_move(
-                moves,
-                Move::new(
-                    king_square, 
-                    king_side_castling_square, 
-                    king_side_castling_square, 
-                    MoveType::CastlingKingSide,
-                )
-            );
+        let castling_path = board.get_castling_path(current_color, castling_side);
+        if let Some(check_info) = &stage.check_info {
+            if !check_info.is_castling_check(board, king_square, castling_path) {
+                continue;
+            }
         }
-    }
 
-    if castling::can_queen_side(*castling_state) {
-        let queen_side_castling_mask = get_queen_side_castling_mask(current_color);
+        add_move(
+            moves,
+            Move::new(
+                king_square, 
+                castling_path.king_target_square, 
+                castling_type,
+            )
+        );
+    }
+}
 
-        let queen_side_castling_king_path_mask = get_queen_side_castling_path_mask(current_color);
+/// Returns true if the castling right is kept, the path is free and the king doesn't pass through an attacked square,
+/// the king must not be in check
+pub(crate) fn can_castle(board: &Board, current_color: usize, king_square: usize, castling_side: u8, attacked_squares_bb: u64, all_occ: u64) -> bool {
+    if board.get_castling_state(current_color) & castling_side == 0 {
+        return false;
+    }
 
-        if (queen_side_castling_mask & all_occ) == 0 
-        && (queen_side_castling_king_path_mask & attacked_squares_bb) == 0 {
-            let queen_side_castling_square = board::get_queen_side_square(current_color);
-            add_move(
-                moves,
-                Move::new(
-                    king_square, 
-                    queen_side_castling_square, 
-                    queen_side_castling_square, 
-                    MoveType::CastlingQueenSide,
-                )
-            );
-        }
+    let castling_path = board.get_castling_path(current_color, castling_side);
+    if (castling_path.empty_mask & all_occ) != 0 
+    || (castling_path.king_path_mask & attacked_squares_bb) != 0 {
+        return false;
     }
+
+    !(board.is_chess960() && is_castling_rook_shielding_king(board, current_color, king_square, castling_path, all_occ))
+}
+
+/// In Chess960 the castling rook can stand between the king's target square and an enemy rook or queen
+/// on the back rank, so the target square doesn't look attacked until the rook leaves
+fn is_castling_rook_shielding_king(board: &Board, current_color: usize, king_square: usize, castling_path: &CastlingPath, all_occ: u64) -> bool {
+    let opposite_color = 1 - current_color;
+    let occupancy_after_castling = (all_occ 
+        & !bitboards::get_bit_from_square(king_square) 
+        & !bitboards::get_bit_from_square(castling_path.rook_square))
+        | bitboards::get_bit_from_square(castling_path.rook_target_square);
+    let enemy_rooks_bb = board.get_piece_bitboard(opposite_color, piece::ROOK) | board.get_piece_bitboard(opposite_color, piece::QUEEN);
+
+    precomputed_data::ROOK_MAGIC_LOOKUP_TABLE.get_pseudo_legal_move_bb(castling_path.king_target_square, occupancy_after_castling) & enemy_rooks_bb != 0
 }
 
-fn generate_pawns(moves: &mut Vec<Move>, board: &Board, attack_calculator: &AttackCalculator, current_color: usize, all_occ: u64, all_opposite_occ: u64, check_block_bb: u64, pin_bbs: &[u64; 4], is_check: bool) {
+fn generate_pawns<const N: usize>(moves: &mut MoveList<N>, board: &Board, context: &GenContext) {
+    let GenContext { stage, attack_calculator, current_color, all_occ, all_opposite_occ, .. } = *context;
+    let check_block_bb = attack_calculator.check_block_bb;
+    let is_check = context.is_single_check();
+    let pin_bbs = &attack_calculator.pins_bbs;
     let not_all_occ = !all_occ;
     
     let shift_amount = get_pawn_shift_amount(current_color);
@@ -219,13 +399,12 @@ fn generate_pawns(moves: &mut Vec<Move>, board: &Board, attack_calculator: &Atta
     let forbidden_en_passant_square = attack_calculator.forbidden_en_passant_square;
     let is_en_passant_possible = board.is_en_passant_possible();
     // Only compute these values if en-passant is possible
-    let (en_passant_square, en_passant_capture, en_passant_square_bb) = if is_en_passant_possible {
-        let square = board.en_passant_pawn_square();
+    let (en_passant_capture, en_passant_square_bb) = if is_en_passant_possible {
         let capture = bitboards::get_bit_from_square(board.en_passant_capture_square());
-        let square_bb = bitboards::get_bit_from_square(square);
-        (square, capture, square_bb)
+        let square_bb = bitboards::get_bit_from_square(board.en_passant_pawn_square());
+        (capture, square_bb)
     } else {
-        (0, 0, 0)
+        (0, 0)
     };
 
     let en_passant_mask_or_check = en_passant_capture | check_block_bb;
@@ -250,6 +429,16 @@ fn generate_pawns(moves: &mut Vec<Move>, board: &Board, attack_calculator: &Atta
     // copying before the check pruning becuase if a pawn can't move one square to block the 
     // check, it doesn't mean it can't move two squares
 
+    // the promotions belong to the captures stage
+    let mut push_targets_bb = 0;
+    if stage.include_quiets {
+        push_targets_bb |= !promotion_rank_bb;
+    }
+    if stage.include_captures {
+        push_targets_bb |= promotion_rank_bb;
+    }
+    pawn_one_square_bb &= push_targets_bb;
+
     if is_check {
         pawn_one_square_bb &= check_block_bb;
     } // Pawns must block the check if there is a check
@@ -261,13 +450,16 @@ fn generate_pawns(moves: &mut Vec<Move>, board: &Board, attack_calculator: &Atta
 
         let start_square = (target_square as i8 - shift_amount) as usize;
 
+        if stage.check_info.is_some() && target_square_bb & stage.get_check_targets_bb(piece::PAWN, start_square) == 0 {
+            continue;
+        }
+
         if (promotion_rank_bb & target_square_bb) == 0 {
             add_move(
                 moves,
                 Move::new(
                     start_square, 
                     target_square, 
-                    target_square, 
                     MoveType::Regular,
                 )
             );
@@ -277,7 +469,6 @@ fn generate_pawns(moves: &mut Vec<Move>, board: &Board, attack_calculator: &Atta
         add_promotion_moves(
             moves,
             start_square, 
-            target_square, 
             target_square
         );
     }
@@ -297,23 +488,34 @@ fn generate_pawns(moves: &mut Vec<Move>, board: &Board, attack_calculator: &Atta
         pawn_two_squares_bb &= check_block_bb;
     } // Pawns must block the check if there is a check
 
+    if !stage.include_quiets {
+        pawn_two_squares_bb = 0;
+    }
+
     while pawn_two_squares_bb != 0 {
         let target_square = bitboards::get_ls1b(pawn_two_squares_bb);
         pawn_two_squares_bb &= pawn_two_squares_bb - 1;
 
         let start_square = (target_square as i8 - two_squares_shift_amount) as usize;
 
+        if stage.check_info.is_some() && bitboards::get_bit_from_square(target_square) & stage.get_check_targets_bb(piece::PAWN, start_square) == 0 {
+            continue;
+        }
+
         add_move(
             moves,
             Move::new(
                 start_square, 
                 target_square, 
-                target_square, 
                 MoveType::PawnDoubleMove
             )
         );
     }
 
+    if !stage.include_captures {
+        return;
+    }
+
     let diagonal_irrelevant_pins = unsafe {
         *pin_bbs.get_unchecked(0)
         | *pin_bbs.get_unchecked(1)
@@ -354,10 +556,10 @@ fn generate_pawns(moves: &mut Vec<Move>, board: &Board, attack_calculator: &Atta
             continue;
         }
 
-        let capture_square = if is_en_passant {
-            en_passant_square
+        let move_type = if is_en_passant {
+            MoveType::EnPassant
         } else {
-            target_square
+            MoveType::Regular
         };
 
         if (promotion_rank_bb & target_square_bb) == 0 {
@@ -366,8 +568,7 @@ fn generate_pawns(moves: &mut Vec<Move>, board: &Board, attack_calculator: &Atta
                 Move::new(
                     start_square, 
                     target_square, 
-                    capture_square, 
-                    MoveType::Regular
+                    move_type
                 )
             );
             continue;
@@ -376,8 +577,7 @@ fn generate_pawns(moves: &mut Vec<Move>, board: &Board, attack_calculator: &Atta
         add_promotion_moves(
             moves,
             start_square, 
-            target_square, 
-            capture_square
+            target_square
         );
     }
 
@@ -410,10 +610,10 @@ fn generate_pawns(moves: &mut Vec<Move>, board: &Board, attack_calculator: &Atta
             continue;
         }
 
-        let capture_square = if is_en_passant {
-            en_passant_square
+        let move_type = if is_en_passant {
+            MoveType::EnPassant
         } else {
-            target_square
+            MoveType::Regular
         };
 
         if (promotion_rank_bb & target_square_bb) == 0 {
@@ -422,8 +622,7 @@ fn generate_pawns(moves: &mut Vec<Move>, board: &Board, attack_calculator: &Atta
                 Move::new(
                     start_square, 
                     target_square, 
-                    capture_square, 
-                    MoveType::Regular
+                    move_type
                 )
             );
             continue;
@@ -432,13 +631,15 @@ fn generate_pawns(moves: &mut Vec<Move>, board: &Board, attack_calculator: &Atta
         add_promotion_moves(
             moves,
             start_square, 
-            target_square, 
-            capture_square
+            target_square
         );
     }
 }
 
-fn generate_knights(moves: &mut Vec<Move>, board: &Board, current_color: usize, not_all_current_occ: u64, check_block_bb: u64, pins_bb: u64) {
+fn generate_knights<const N: usize>(moves: &mut MoveList<N>, board: &Board, context: &GenContext) {
+    let GenContext { stage, current_color, .. } = *context;
+    let check_block_bb = context.attack_calculator.check_block_bb;
+    let pins_bb = context.attack_calculator.pins_bbs.iter().fold(0, |pins_bb, &pin_bb| pins_bb | pin_bb);
     let mut knight_bb = board.get_piece_bitboard(current_color, piece::KNIGHT);
 
     knight_bb &= !pins_bb; // We can prune the move generation for the pinned knights 
@@ -450,7 +651,8 @@ fn generate_knights(moves: &mut Vec<Move>, board: &Board, current_color: usize,
 
         let mut pseudo_moves_bb = precomputed_data::SQUARE_DATA
             .get_bb_for_knight(start_square) 
-            & not_all_current_occ;
+            & stage.target_bb
+            & stage.get_check_targets_bb(piece::KNIGHT, start_square);
 
         if check_block_bb != 0 {
             pseudo_moves_bb &= check_block_bb;
@@ -465,7 +667,6 @@ fn generate_knights(moves: &mut Vec<Move>, board: &Board, current_color: usize,
                 Move::new(
                     start_square, 
                     target_square, 
-                    target_square, 
                     MoveType::Regular
                 )
             );
@@ -473,7 +674,18 @@ fn generate_knights(moves: &mut Vec<Move>, board: &Board, current_color: usize,
     }
 }
 
-fn generate_sliders(moves: &mut Vec<Move>, board: &Board, slider_type: usize, irrelevant_pins_bb: u64, relevant_pin_bb_1: u64, relevant_pin_bb_2: u64, relevant_pin_index_1: usize, relevant_pin_index_2: usize, magic_lookup_table: &Lazy<Box<MagicLookupTable>>, current_color: usize, is_check: bool, check_block_bb: u64, all_occ: u64, not_all_occ_current_color: u64) {    
+/// The slider can't move at all when pinned along one of the irrelevant directions,
+/// and only along the pin when pinned along one of the relevant ones
+fn generate_sliders<const N: usize>(moves: &mut MoveList<N>, board: &Board, context: &GenContext, slider_type: usize, relevant_pin_indexes: [usize; 2], irrelevant_pin_indexes: [usize; 2], magic_lookup_table: &Lazy<Box<MagicLookupTable>>) {
+    let GenContext { stage, current_color, all_occ, .. } = *context;
+    let check_block_bb = context.attack_calculator.check_block_bb;
+    let is_check = context.is_single_check();
+    let pin_bbs = &context.attack_calculator.pins_bbs;
+    let [relevant_pin_index_1, relevant_pin_index_2] = relevant_pin_indexes;
+    let relevant_pin_bb_1 = unsafe { *pin_bbs.get_unchecked(relevant_pin_index_1) };
+    let relevant_pin_bb_2 = unsafe { *pin_bbs.get_unchecked(relevant_pin_index_2) };
+    let irrelevant_pins_bb = unsafe { *pin_bbs.get_unchecked(irrelevant_pin_indexes[0]) | *pin_bbs.get_unchecked(irrelevant_pin_indexes[1]) };
+
     let mut slider_bb = board.get_piece_bitboard(current_color, slider_type) & !irrelevant_pins_bb;
 
     while slider_bb != 0 {
@@ -485,7 +697,8 @@ fn generate_sliders(moves: &mut Vec<Move>, board: &Board, slider_type: usize, ir
             .get_pseudo_legal_move_bb(
                 start_square, 
                 all_occ
-            ) & not_all_occ_current_color;
+            ) & stage.target_bb
+            & stage.get_check_targets_bb(slider_type, start_square);
 
         if is_check {
             pseudo_moves_bb &= check_block_bb;
@@ -507,7 +720,6 @@ fn generate_sliders(moves: &mut Vec<Move>, board: &Board, slider_type: usize, ir
                 Move::new(
                     start_square, 
                     target_square, 
-                    target_square, 
                     MoveType::Regular
                 )
             );
@@ -515,7 +727,11 @@ fn generate_sliders(moves: &mut Vec<Move>, board: &Board, slider_type: usize, ir
     }
 }
 
-fn generate_queens(moves: &mut Vec<Move>, board: &Board, current_color: usize, all_occ: u64, not_all_occ_current_color: u64, is_single_check: bool, squares_to_block_check_bb: u64, pin_bbs: &[u64; 4]) {
+fn generate_queens<const N: usize>(moves: &mut MoveList<N>, board: &Board, context: &GenContext) {
+    let GenContext { stage, current_color, all_occ, .. } = *context;
+    let is_single_check = context.is_single_check();
+    let squares_to_block_check_bb = context.attack_calculator.check_block_bb;
+    let pin_bbs = &context.attack_calculator.pins_bbs;
     let mut queen_bb = board.get_piece_bitboard(current_color, piece::QUEEN);
 
     while queen_bb != 0 {
@@ -529,7 +745,8 @@ fn generate_queens(moves: &mut Vec<Move>, board: &Board, current_color: usize, a
             .get_pseudo_legal_move_bb(start_square, all_occ) |
         precomputed_data::BISHOP_MAGIC_LOOKUP_TABLE
             .get_pseudo_legal_move_bb(start_square, all_occ)
-        ) & not_all_occ_current_color;
+        ) & stage.target_bb
+        & stage.get_check_targets_bb(piece::QUEEN, start_square);
 
         if is_single_check {
             pseudo_moves_bb &= squares_to_block_check_bb;
@@ -551,7 +768,6 @@ fn generate_queens(moves: &mut Vec<Move>, board: &Board, current_color: usize, a
                 Move::new(
                     start_square, 
                     target_square, 
-                    target_square, 
                     MoveType::Regular
                 )
             );
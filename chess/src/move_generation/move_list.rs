@@ -0,0 +1,147 @@
+use std::{fmt::{self, Debug}, ops::{Deref, DerefMut}};
+
+use crate::r#move::Move;
+
+use super::move_gen::{MAX_CRAZYHOUSE_MOVES_PER_POS, MAX_MOVES_PER_POS};
+
+/// List of the moves of a single position stored on the stack, so no allocations are made
+/// while generating the moves
+///
+/// Every move has a score slot used for the move ordering, the scores are not changed by the
+/// move generation and are only meaningful after they were set with *set_score*
+///
+/// The capacity is enough for every variant without pockets, Crazyhouse needs a *CrazyhouseMoveList*
+#[derive(Clone)]
+pub struct MoveList<const N: usize = MAX_MOVES_PER_POS> {
+    moves: [Move; N],
+    scores: [i32; N],
+    len: usize,
+}
+
+/// Move list long enough for the drops of Crazyhouse
+pub type CrazyhouseMoveList = MoveList<MAX_CRAZYHOUSE_MOVES_PER_POS>;
+
+impl<const N: usize> MoveList<N> {
+    pub fn new() -> Self {
+        Self {
+            moves: [Move::from_packed(0); N],
+            scores: [0; N],
+            len: 0,
+        }
+    }
+
+    pub fn push(&mut self, mov: Move) {
+        assert!(self.len < N, "Move list is full");
+        unsafe { *self.moves.get_unchecked_mut(self.len) = mov; }
+        self.len += 1;
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Keeps only the first *len* moves, nothing happens if there are fewer moves
+    pub fn truncate(&mut self, len: usize) {
+        self.len = self.len.min(len);
+    }
+
+    pub fn get_score(&self, index: usize) -> i32 {
+        debug_assert!(index < self.len, "Move index out of bounds");
+        unsafe { *self.scores.get_unchecked(index) }
+    }
+
+    pub fn set_score(&mut self, index: usize, score: i32) {
+        debug_assert!(index < self.len, "Move index out of bounds");
+        unsafe { *self.scores.get_unchecked_mut(index) = score; }
+    }
+
+    /// Sets the score of every move with the provided function
+    pub fn score_moves(&mut self, mut get_score: impl FnMut(Move) -> i32) {
+        for index in 0..self.len {
+            self.scores[index] = get_score(self.moves[index]);
+        }
+    }
+
+    /// Swaps the two moves together with their scores
+    pub fn swap(&mut self, first_index: usize, second_index: usize) {
+        debug_assert!(first_index < self.len && second_index < self.len, "Move index out of bounds");
+        self.moves.swap(first_index, second_index);
+        self.scores.swap(first_index, second_index);
+    }
+
+    /// Moves the best scored move from the index onwards to the index and returns it,
+    /// so the moves can be tried one by one without sorting the whole list
+    pub fn pick_best(&mut self, index: usize) -> Move {
+        debug_assert!(index < self.len, "Move index out of bounds");
+        let mut best_index = index;
+        for other_index in index + 1..self.len {
+            if self.scores[other_index] > self.scores[best_index] {
+                best_index = other_index;
+            }
+        }
+        self.swap(index, best_index);
+        self.moves[index]
+    }
+
+    /// Sorts the moves from the best scored to the worst one, the moves with equal scores keep their order
+    pub fn sort_by_score(&mut self) {
+        // insertion sort, the lists are short and usually partially ordered already
+        for index in 1..self.len {
+            let mov = self.moves[index];
+            let score = self.scores[index];
+            let mut insert_index = index;
+            while insert_index > 0 && self.scores[insert_index - 1] < score {
+                self.moves[insert_index] = self.moves[insert_index - 1];
+                self.scores[insert_index] = self.scores[insert_index - 1];
+                insert_index -= 1;
+            }
+            self.moves[insert_index] = mov;
+            self.scores[insert_index] = score;
+        }
+    }
+}
+
+impl<const N: usize> Default for MoveList<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Deref for MoveList<N> {
+    type Target = [Move];
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.moves.get_unchecked(..self.len) }
+    }
+}
+
+impl<const N: usize> DerefMut for MoveList<N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { self.moves.get_unchecked_mut(..self.len) }
+    }
+}
+
+impl<const N: usize> Debug for MoveList<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, const N: usize> IntoIterator for &'a MoveList<N> {
+    type Item = &'a Move;
+    type IntoIter = std::slice::Iter<'a, Move>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<const N: usize> FromIterator<Move> for MoveList<N> {
+    fn from_iter<T: IntoIterator<Item = Move>>(iter: T) -> Self {
+        let mut moves = Self::new();
+        for mov in iter {
+            moves.push(mov);
+        }
+        moves
+    }
+}
@@ -0,0 +1,101 @@
+/* Generates every move of the side to move without caring whether its own king is left attacked.
+The variants which don't follow the regular check rules (like Atomic) filter these moves with their own legality rules */
+use crate::{bitboards, board::Board, r#move::{Move, MoveType}, piece, precomputed_data};
+
+use super::{move_gen, move_list::MoveList};
+
+/// Generates the moves of every piece except the castling, the king can capture like any other piece
+pub fn generate_moves<const N: usize>(moves: &mut MoveList<N>, board: &Board) {
+    let current_color = board.get_current_color();
+    let all_occ = board.get_all_occupied_squares();
+    let not_all_current_occ = !board.get_all_occupied_squares_for_color(current_color);
+
+    generate_pawns(moves, board, current_color, all_occ);
+
+    for piece_type in [piece::KNIGHT, piece::BISHOP, piece::ROOK, piece::QUEEN, piece::KING] {
+        let mut piece_bb = board.get_piece_bitboard(current_color, piece_type);
+
+        while piece_bb != 0 {
+            let start_square = bitboards::get_ls1b(piece_bb);
+            piece_bb &= piece_bb - 1;
+
+            let mut pseudo_moves_bb = get_attacks_bb(piece_type, start_square, all_occ) & not_all_current_occ;
+
+            while pseudo_moves_bb != 0 {
+                let target_square = bitboards::get_ls1b(pseudo_moves_bb);
+                pseudo_moves_bb &= pseudo_moves_bb - 1;
+
+                move_gen::add_move(
+                    moves,
+                    Move::new(
+                        start_square,
+                        target_square,
+                        MoveType::Regular,
+                    )
+                );
+            }
+        }
+    }
+}
+
+/// Returns the squares attacked by the piece (except pawns) standing on the provided square
+pub fn get_attacks_bb(piece_type: usize, square: usize, all_occ: u64) -> u64 {
+    match piece_type {
+        piece::KING => precomputed_data::SQUARE_DATA.get_bb_for_king(square),
+        piece::KNIGHT => precomputed_data::SQUARE_DATA.get_bb_for_knight(square),
+        piece::BISHOP => precomputed_data::BISHOP_MAGIC_LOOKUP_TABLE.get_pseudo_legal_move_bb(square, all_occ),
+        piece::ROOK => precomputed_data::ROOK_MAGIC_LOOKUP_TABLE.get_pseudo_legal_move_bb(square, all_occ),
+        piece::QUEEN => precomputed_data::BISHOP_MAGIC_LOOKUP_TABLE.get_pseudo_legal_move_bb(square, all_occ)
+            | precomputed_data::ROOK_MAGIC_LOOKUP_TABLE.get_pseudo_legal_move_bb(square, all_occ),
+        _ => 0,
+    }
+}
+
+/// Adds the move of the pawn, or all four promotions if it reaches the last rank
+fn add_pawn_move<const N: usize>(moves: &mut MoveList<N>, current_color: usize, start_square: usize, target_square: usize) {
+    if move_gen::get_pawn_promotion_rank(current_color) & bitboards::get_bit_from_square(target_square) != 0 {
+        move_gen::add_promotion_moves(moves, start_square, target_square);
+    } else {
+        move_gen::add_move(moves, Move::new(start_square, target_square, MoveType::Regular));
+    }
+}
+
+fn generate_pawns<const N: usize>(moves: &mut MoveList<N>, board: &Board, current_color: usize, all_occ: u64) {
+    let shift_amount = move_gen::get_pawn_shift_amount(current_color);
+    let all_opposite_occ = board.get_all_occupied_squares_for_color(1 - current_color);
+    let en_passant_capture_bb = if board.is_en_passant_possible() {
+        bitboards::get_bit_from_square(board.en_passant_capture_square())
+    } else {
+        0
+    };
+
+    let mut pawn_bb = board.get_piece_bitboard(current_color, piece::PAWN);
+    while pawn_bb != 0 {
+        let start_square = bitboards::get_ls1b(pawn_bb);
+        pawn_bb &= pawn_bb - 1;
+
+        let one_square_target = (start_square as i8 + shift_amount) as usize;
+        if all_occ & bitboards::get_bit_from_square(one_square_target) == 0 {
+            add_pawn_move(moves, current_color, start_square, one_square_target);
+
+            let two_squares_target = (one_square_target as i8 + shift_amount) as usize;
+            if move_gen::get_pawn_start_rank(current_color) & bitboards::get_bit_from_square(start_square) != 0
+            && all_occ & bitboards::get_bit_from_square(two_squares_target) == 0 {
+                move_gen::add_move(moves, Move::new(start_square, two_squares_target, MoveType::PawnDoubleMove));
+            }
+        }
+
+        let mut captures_bb = precomputed_data::SQUARE_DATA.get_bb_for_pawn_attacks(current_color, start_square)
+            & (all_opposite_occ | en_passant_capture_bb);
+        while captures_bb != 0 {
+            let target_square = bitboards::get_ls1b(captures_bb);
+            captures_bb &= captures_bb - 1;
+
+            if bitboards::get_bit_from_square(target_square) == en_passant_capture_bb {
+                move_gen::add_move(moves, Move::new(start_square, target_square, MoveType::EnPassant));
+            } else {
+                add_pawn_move(moves, current_color, start_square, target_square);
+            }
+        }
+    }
+}
@@ -1,18 +1,13 @@
 use std::time::Instant;
 
-use crate::{board::Board, config, r#move::Move, move_generation::move_gen};
+use crate::{board::Board, config, epd::EpdRecord, move_generation::{move_gen::{self, GenType, MAX_CRAZYHOUSE_MOVES_PER_POS, MAX_MOVES_PER_POS}, move_list::{CrazyhouseMoveList, MoveList}}};
 
 pub mod perft_node;
 
 pub fn run_perft(depth: u8, board: &mut Board) -> u64 {
     let start = Instant::now();
 
-    
-    let mut move_buffer: Vec<Vec<Move>> = (0..depth)
-        .map(|_| Vec::with_capacity(move_gen::MAX_MOVES_PER_POS))
-        .collect();
-
-    let result = count_nodes(depth, board, &mut move_buffer);
+    let result = count_variant_nodes(depth, board);
     let duration = start.elapsed();
 
     if core::config::DO_TERMINAL_OUTPUT {
@@ -23,13 +18,113 @@ pub fn run_perft(depth: u8, board: &mut Board) -> u64 {
     result
 }
 
-fn count_nodes(depth: u8, board: &mut Board, move_buffer: &mut [Vec<Move>]) -> u64 {
+/// Same as *run_perft* but the moves of every node are generated stage by stage,
+/// the captures and the quiet moves or the evasions when in check
+///
+/// The stages are checked against the full move list on every node, the quiet checks included,
+/// so a wrong stage panics
+pub fn run_staged_perft(depth: u8, board: &mut Board) -> u64 {
+    debug_assert!(depth > 0, "Depth must be greater than 0");
+
+    let mut all_moves = CrazyhouseMoveList::new();
+    move_gen::generate_moves(&mut all_moves, board);
+
+    let mut staged_moves = CrazyhouseMoveList::new();
+    let is_in_check = move_gen::is_in_check(board);
+    if is_in_check {
+        move_gen::generate_staged_moves(&mut staged_moves, board, GenType::Evasions);
+    } else {
+        move_gen::generate_staged_moves(&mut staged_moves, board, GenType::Captures);
+        move_gen::generate_staged_moves(&mut staged_moves, board, GenType::Quiets);
+    }
+
+    let mut sorted_all_moves: Vec<u16> = all_moves.iter().map(|mov| mov.get_packed()).collect();
+    let mut sorted_staged_moves: Vec<u16> = staged_moves.iter().map(|mov| mov.get_packed()).collect();
+    sorted_all_moves.sort_unstable();
+    sorted_staged_moves.sort_unstable();
+    assert_eq!(sorted_all_moves, sorted_staged_moves, "Staged moves differ from all moves in {}", board.to_fen());
+
+    let mut quiet_moves = CrazyhouseMoveList::new();
+    let mut quiet_checks = CrazyhouseMoveList::new();
+    move_gen::generate_staged_moves(&mut quiet_moves, board, GenType::Quiets);
+    move_gen::generate_staged_moves(&mut quiet_checks, board, GenType::QuietChecks);
+    for &mov in quiet_moves.iter() {
+        let move_record = board.make_move(mov);
+        let gives_check = move_gen::is_in_check(board);
+        board.undo_move(move_record);
+        assert_eq!(gives_check, quiet_checks.contains(&mov), "Quiet check {} is wrong in {}", mov, board.to_fen());
+    }
+    assert!(quiet_checks.iter().all(|mov| quiet_moves.contains(mov)), "Quiet checks are not quiet in {}", board.to_fen());
+
+    if depth == 1 {
+        return staged_moves.len() as u64;
+    }
+
+    let mut nodes = 0;
+    for &mov in staged_moves.iter() {
+        let move_record = board.make_move(mov);
+        nodes += run_staged_perft(depth - 1, board);
+        board.undo_move(move_record);
+    }
+    nodes
+}
+
+/// A position of a perft suite whose node count differs from the expected one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PerftMismatch {
+    pub fen: String,
+    pub depth: u8,
+    pub expected_nodes: u64,
+    pub nodes: u64,
+}
+
+/// Checks the node counts of every EPD record (the *D1*-*D9* opcodes) up to the provided depth
+pub fn run_perft_suite(records: &[EpdRecord], max_depth: u8) -> Vec<PerftMismatch> {
+    let mut mismatches = Vec::new();
+
+    for record in records.iter() {
+        let mut board = record.get_board().clone();
+        for (depth, expected_nodes) in record.get_perft_counts().into_iter().filter(|&(depth, _)| depth <= max_depth) {
+            let nodes = count_variant_nodes(depth, &mut board);
+            if core::config::DO_TERMINAL_OUTPUT {
+                println!("{} depth {}: {} (expected {})", board.to_fen(), depth, nodes, expected_nodes);
+            }
+            if nodes != expected_nodes {
+                mismatches.push(PerftMismatch {
+                    fen: board.to_fen(),
+                    depth,
+                    expected_nodes,
+                    nodes,
+                });
+            }
+        }
+    }
+
+    mismatches
+}
+
+/// Counts the nodes with the move lists long enough for the variant, only Crazyhouse needs the longer ones
+fn count_variant_nodes(depth: u8, board: &mut Board) -> u64 {
+    if board.get_variant().has_pockets() {
+        count_nodes(depth, board, &mut new_move_buffer::<MAX_CRAZYHOUSE_MOVES_PER_POS>(depth))
+    } else {
+        count_nodes(depth, board, &mut new_move_buffer::<MAX_MOVES_PER_POS>(depth))
+    }
+}
+
+fn new_move_buffer<const N: usize>(depth: u8) -> Vec<MoveList<N>> {
+    (0..depth)
+        .map(|_| MoveList::new())
+        .collect()
+}
+
+fn count_nodes<const N: usize>(depth: u8, board: &mut Board, move_buffer: &mut [MoveList<N>]) -> u64 {
     debug_assert!(depth > 0, "Depth must be greater than 0");
     
     let (current_moves, remaining_buffer) = move_buffer.split_at_mut(1);
-    let mut current_moves = unsafe { current_moves.get_unchecked_mut(0) };
+    let current_moves = unsafe { current_moves.get_unchecked_mut(0) };
     current_moves.clear();
-    move_gen::generate_moves(&mut current_moves, board);
+    move_gen::generate_moves(current_moves, board);
 
     if depth == 1 {
         return current_moves.len() as u64;
@@ -40,15 +135,15 @@ fn count_nodes(depth: u8, board: &mut Board, move_buffer: &mut [Vec<Move>]) -> u
     if depth == 2 {
         let mut nodes = 0;
         let (child_moves, _) = remaining_buffer.split_at_mut(1);
-        let mut child_moves = unsafe { child_moves.get_unchecked_mut(0) };
+        let child_moves = unsafe { child_moves.get_unchecked_mut(0) };
 
-        for mov in current_moves.drain(..) {
+        for &mov in current_moves.iter() {
             if do_output {
                 print!("{}: ", mov);
             }
             let move_record = board.make_move(mov);
             child_moves.clear();
-            move_gen::generate_moves(&mut child_moves, board);
+            move_gen::generate_moves(child_moves, board);
             board.undo_move(move_record);
             let child_nodes = child_moves.len() as u64;
             nodes += child_nodes;
@@ -62,7 +157,7 @@ fn count_nodes(depth: u8, board: &mut Board, move_buffer: &mut [Vec<Move>]) -> u
 
     let mut nodes = 0;
 
-    for mov in current_moves.drain(..) {
+    for &mov in current_moves.iter() {
         if do_output {
             print!("{}: ", mov);
         }
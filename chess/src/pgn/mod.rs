@@ -0,0 +1,19 @@
+pub mod pgn_error;
+pub mod pgn_game;
+mod pgn_parser;
+pub mod pgn_reader;
+mod pgn_writer;
+
+use pgn_error::PgnError;
+use pgn_game::PgnGame;
+use pgn_parser::PgnParser;
+
+/// Reads every game of the provided PGN text, stops at the first invalid game
+pub fn read_games(pgn: &str) -> Result<Vec<PgnGame>, PgnError> {
+    let mut parser = PgnParser::new(pgn);
+    let mut games = Vec::new();
+    while let Some(game) = parser.read_game() {
+        games.push(game?);
+    }
+    Ok(games)
+}
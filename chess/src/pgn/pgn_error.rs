@@ -0,0 +1,50 @@
+use std::{error::Error, fmt::Display};
+
+use crate::chess_parse_error::ChessParseError;
+
+/// Describes what exactly was wrong with a PGN game
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PgnError {
+    /// A tag pair isn't written as *\[Name "Value"]*
+    InvalidTag(String),
+    /// The text ended inside of a *{comment}*
+    UnterminatedComment,
+    /// A *$* isn't followed by a number from 0 to 255
+    InvalidNag(String),
+    /// A NAG was written before any move it could annotate
+    NagWithoutMove(u8),
+    /// A character that can't start any PGN token
+    UnexpectedChar(char),
+    /// A *)* without the matching *(*
+    UnexpectedVariationEnd,
+    /// The game ended inside of a variation
+    UnterminatedVariation,
+    /// A variation was opened before any move it could be an alternative to
+    VariationWithoutMove,
+    /// The *FEN* tag doesn't contain a valid position
+    InvalidFen(ChessParseError),
+    /// The SAN move at the provided ply (starting from 1) can't be played in its position
+    IllegalMove { ply: usize, san: String, error: ChessParseError },
+    /// Reading from the underlying source has failed
+    Io(String),
+}
+
+impl Display for PgnError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidTag(tag) => write!(f, "invalid tag pair: \"{}\"", tag),
+            Self::UnterminatedComment => write!(f, "unterminated comment"),
+            Self::InvalidNag(nag) => write!(f, "invalid NAG: \"{}\"", nag),
+            Self::NagWithoutMove(nag) => write!(f, "NAG ${} doesn't follow any move", nag),
+            Self::UnexpectedChar(sym) => write!(f, "unexpected character: '{}'", sym),
+            Self::UnexpectedVariationEnd => write!(f, "unexpected end of variation"),
+            Self::UnterminatedVariation => write!(f, "unterminated variation"),
+            Self::VariationWithoutMove => write!(f, "variation doesn't follow any move"),
+            Self::InvalidFen(error) => write!(f, "invalid FEN tag: {}", error),
+            Self::IllegalMove { ply, san, error } => write!(f, "ply {}: \"{}\" can't be played ({})", ply, san, error),
+            Self::Io(error) => write!(f, "failed to read PGN: {}", error),
+        }
+    }
+}
+
+impl Error for PgnError {}
@@ -0,0 +1,164 @@
+use core::game::GameState;
+
+use crate::{board::Board, board_representation, r#move::Move, variant::Variant};
+
+use super::{pgn_error::PgnError, pgn_parser::PgnParser};
+
+/// The tags every PGN game must have, written first and in this exact order
+pub const SEVEN_TAG_ROSTER: [&str; 7] = ["Event", "Site", "Date", "Round", "White", "Black", "Result"];
+
+/// Returns the value the roster tag gets when it is unknown
+pub fn get_roster_tag_default(name: &str) -> &'static str {
+    match name {
+        "Date" => "????.??.??",
+        "Result" => "*",
+        _ => "?",
+    }
+}
+
+/// A single move of the game together with everything annotating it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgnMoveNode {
+    pub mov: Move,
+    /// Numeric Annotation Glyphs, *!* and *?* suffixes are stored as NAGs 1-6
+    pub nags: Vec<u8>,
+    /// Comments written before the move, only kept for the first move of a variation
+    pub starting_comments: Vec<String>,
+    /// Comments written after the move
+    pub comments: Vec<String>,
+    /// Alternatives to this move, each one starts from the position before it
+    pub variations: Vec<Vec<PgnMoveNode>>,
+}
+
+impl PgnMoveNode {
+    pub fn new(mov: Move) -> Self {
+        Self {
+            mov,
+            nags: Vec::new(),
+            starting_comments: Vec::new(),
+            comments: Vec::new(),
+            variations: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgnGame {
+    /// Tag pairs in the order they were read or set
+    tags: Vec<(String, String)>,
+    /// Comments written before the first move of the game
+    pub starting_comments: Vec<String>,
+    /// The main line of the game
+    pub moves: Vec<PgnMoveNode>,
+    /// The result given by the game termination marker, *Result* tag is ignored when writing
+    pub game_state: GameState,
+}
+
+impl Default for PgnGame {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PgnGame {
+    /// Creates an empty game with the seven tag roster set to unknown values
+    pub fn new() -> Self {
+        Self {
+            tags: SEVEN_TAG_ROSTER.iter()
+                .map(|&name| (name.to_string(), get_roster_tag_default(name).to_string()))
+                .collect(),
+            starting_comments: Vec::new(),
+            moves: Vec::new(),
+            game_state: GameState::InProgress,
+        }
+    }
+
+    /// Creates a game from the legal moves played from the provided position
+    ///
+    /// The *FEN* and *SetUp* tags are only set if the game didn't start from the default position
+    pub fn from_moves(starting_board: &Board, moves: &[Move], game_state: GameState) -> Self {
+        let mut game = Self::new();
+
+        let starting_fen = starting_board.to_fen();
+        if starting_fen != board_representation::DEFAULT_FEN {
+            game.set_tag("SetUp", "1");
+            game.set_tag("FEN", &starting_fen);
+        }
+        if starting_board.get_variant() != Variant::Standard {
+            game.set_tag("Variant", starting_board.get_variant().get_pgn_name());
+        } else if starting_board.is_chess960() {
+            game.set_tag("Variant", "Chess960");
+        }
+
+        game.game_state = game_state;
+        game.set_tag("Result", game_state.get_pgn_result());
+        if game_state.is_finished() {
+            game.set_tag("Termination", game_state.get_pgn_termination());
+        }
+
+        game.moves = moves.iter().map(|&mov| PgnMoveNode::new(mov)).collect();
+        game
+    }
+
+    /// Reads the first game of the provided PGN text
+    pub fn from_pgn(pgn: &str) -> Result<Self, PgnError> {
+        PgnParser::new(pgn).read_game().unwrap_or_else(|| Ok(Self::new()))
+    }
+
+    pub fn get_tags(&self) -> &[(String, String)] {
+        &self.tags
+    }
+
+    pub fn get_tag(&self, name: &str) -> Option<&str> {
+        self.tags.iter()
+            .find(|(tag_name, _)| tag_name == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Replaces the value of the tag or adds the tag to the end if it isn't present
+    pub fn set_tag(&mut self, name: &str, value: &str) {
+        match self.tags.iter_mut().find(|(tag_name, _)| tag_name == name) {
+            Some((_, tag_value)) => *tag_value = value.to_string(),
+            None => self.tags.push((name.to_string(), value.to_string())),
+        }
+    }
+
+    /// Returns the position the game starts from, given by the *FEN* tag or the default one
+    pub fn get_starting_board(&self) -> Result<Board, PgnError> {
+        let mut board = match self.get_tag("FEN") {
+            Some(fen) => Board::try_from_fen(fen).map_err(PgnError::InvalidFen)?,
+            None => Board::from_fen(board_representation::DEFAULT_FEN),
+        };
+        if self.is_chess960() {
+            board.set_chess960(true);
+        }
+        if self.get_variant() != Variant::Standard {
+            board.set_variant(self.get_variant());
+        }
+        Ok(board)
+    }
+
+    /// Returns the variant of the *Variant* tag, the unknown variants are treated as the standard chess
+    pub fn get_variant(&self) -> Variant {
+        self.get_tag("Variant")
+            .and_then(Variant::from_pgn_name)
+            .unwrap_or_default()
+    }
+
+    /// Checks the *Variant* tag for Chess960 and its other common names
+    pub fn is_chess960(&self) -> bool {
+        self.get_tag("Variant").is_some_and(|variant| matches!(
+            variant.to_ascii_lowercase().as_str(),
+            "chess960" | "chess 960" | "fischerandom" | "fischer random" | "fischerrandom"
+        ))
+    }
+
+    /// Returns the position after the last move of the main line
+    pub fn get_final_board(&self) -> Result<Board, PgnError> {
+        let mut board = self.get_starting_board()?;
+        for node in self.moves.iter() {
+            board.make_move(node.mov);
+        }
+        Ok(board)
+    }
+}
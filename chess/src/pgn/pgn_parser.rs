@@ -0,0 +1,334 @@
+use core::game::GameState;
+
+use crate::{board::Board, r#move::move_record::MoveRecord};
+
+use super::{pgn_error::PgnError, pgn_game::{PgnGame, PgnMoveNode}};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum PgnToken<'a> {
+    Tag { name: &'a str, value: String },
+    Comment(&'a str),
+    Nag(u8),
+    VariationStart,
+    VariationEnd,
+    /// The game termination marker: *1-0*, *0-1*, *1/2-1/2* or *\**
+    GameResult(&'a str),
+    San(&'a str),
+}
+
+/// Returns the NAG corresponding to the move suffix annotation
+fn get_nag_from_suffix(suffix: &str) -> Option<u8> {
+    match suffix {
+        "!" => Some(1),
+        "?" => Some(2),
+        "!!" => Some(3),
+        "??" => Some(4),
+        "!?" => Some(5),
+        "?!" => Some(6),
+        _ => None,
+    }
+}
+
+/// Line breaks inside of comments are not meaningful, they are replaced with spaces
+fn normalize_comment(comment: &str) -> String {
+    comment.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+fn is_symbol_char(sym: char) -> bool {
+    sym.is_ascii_alphanumeric() || matches!(sym, '_' | '+' | '#' | '=' | ':' | '-' | '/' | '@')
+}
+
+/// Splits PGN text into tokens, skipping move numbers and escaped lines
+pub(crate) struct PgnLexer<'a> {
+    text: &'a str,
+    position: usize,
+    is_line_start: bool,
+}
+
+impl<'a> PgnLexer<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            position: 0,
+            is_line_start: true,
+        }
+    }
+
+    fn get_rest(&self) -> &'a str {
+        &self.text[self.position..]
+    }
+
+    /// Moves the position to the first char not matching the predicate and returns the skipped text
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+        let rest = self.get_rest();
+        let length = rest.find(|sym| !predicate(sym)).unwrap_or(rest.len());
+        self.position += length;
+        &rest[..length]
+    }
+
+    fn skip_line(&mut self) -> &'a str {
+        self.take_while(|sym| sym != '\n')
+    }
+
+    pub fn next_token(&mut self) -> Option<Result<PgnToken<'a>, PgnError>> {
+        loop {
+            let sym = self.get_rest().chars().next()?;
+            let is_line_start = self.is_line_start;
+            self.is_line_start = sym == '\n';
+
+            match sym {
+                '%' if is_line_start => {
+                    self.skip_line();
+                }
+                '.' => self.position += 1,
+                _ if sym.is_whitespace() => self.position += sym.len_utf8(),
+                '[' => return Some(self.read_tag()),
+                '{' => {
+                    self.position += 1;
+                    let comment = self.take_while(|sym| sym != '}');
+                    if self.get_rest().is_empty() {
+                        return Some(Err(PgnError::UnterminatedComment));
+                    }
+                    self.position += 1;
+                    return Some(Ok(PgnToken::Comment(comment)));
+                }
+                ';' => {
+                    self.position += 1;
+                    return Some(Ok(PgnToken::Comment(self.skip_line())));
+                }
+                '$' => {
+                    self.position += 1;
+                    let nag = self.take_while(|sym| sym.is_ascii_digit());
+                    return Some(nag.parse().map(PgnToken::Nag).map_err(|_| PgnError::InvalidNag(nag.to_string())));
+                }
+                '!' | '?' => {
+                    let suffix = self.take_while(|sym| sym == '!' || sym == '?');
+                    return Some(get_nag_from_suffix(suffix).map(PgnToken::Nag).ok_or_else(|| PgnError::InvalidNag(suffix.to_string())));
+                }
+                '(' => {
+                    self.position += 1;
+                    return Some(Ok(PgnToken::VariationStart));
+                }
+                ')' => {
+                    self.position += 1;
+                    return Some(Ok(PgnToken::VariationEnd));
+                }
+                '*' => {
+                    self.position += 1;
+                    return Some(Ok(PgnToken::GameResult("*")));
+                }
+                // a Crazyhouse pawn drop can start with *@*
+                _ if sym.is_ascii_alphanumeric() || sym == '@' => {
+                    let symbol = self.take_while(is_symbol_char);
+                    if symbol.chars().all(|sym| sym.is_ascii_digit()) {
+                        // move number, the dots after it are skipped as well
+                        continue;
+                    }
+                    if matches!(symbol, "1-0" | "0-1" | "1/2-1/2") {
+                        return Some(Ok(PgnToken::GameResult(symbol)));
+                    }
+                    let rest = self.get_rest();
+                    let trimmed_rest = rest.trim_start_matches(' ');
+                    if trimmed_rest.starts_with("e.p.") {
+                        self.position += rest.len() - trimmed_rest.len() + "e.p.".len();
+                    }
+                    return Some(Ok(PgnToken::San(symbol)));
+                }
+                _ => {
+                    self.position += sym.len_utf8();
+                    return Some(Err(PgnError::UnexpectedChar(sym)));
+                }
+            }
+        }
+    }
+
+    /// Reads a tag pair like *\[White "Carlsen, Magnus"]*, the value can contain escaped *\\"* and *\\\\*
+    fn read_tag(&mut self) -> Result<PgnToken<'a>, PgnError> {
+        let tag_start = self.position;
+        let invalid_tag = |lexer: &mut Self| {
+            lexer.position = tag_start;
+            PgnError::InvalidTag(lexer.skip_line().to_string())
+        };
+
+        self.position += 1;
+        self.take_while(char::is_whitespace);
+        let name = self.take_while(|sym| sym.is_ascii_alphanumeric() || sym == '_');
+        self.take_while(char::is_whitespace);
+        if name.is_empty() || !self.get_rest().starts_with('"') {
+            return Err(invalid_tag(self));
+        }
+        self.position += 1;
+
+        let mut value = String::new();
+        let mut chars = self.get_rest().char_indices();
+        let value_length = loop {
+            match chars.next() {
+                Some((index, '"')) => break index,
+                Some((_, '\\')) => match chars.next() {
+                    Some((_, escaped_sym)) => value.push(escaped_sym),
+                    None => return Err(invalid_tag(self)),
+                },
+                Some((_, '\n')) | None => return Err(invalid_tag(self)),
+                Some((_, sym)) => value.push(sym),
+            }
+        };
+        self.position += value_length + 1;
+
+        self.take_while(|sym| sym.is_whitespace() && sym != '\n');
+        if !self.get_rest().starts_with(']') {
+            return Err(invalid_tag(self));
+        }
+        self.position += 1;
+
+        Ok(PgnToken::Tag { name, value })
+    }
+}
+
+/// Reads games one by one from PGN text, replaying every move to make sure it is legal
+pub(crate) struct PgnParser<'a> {
+    lexer: PgnLexer<'a>,
+    peeked_token: Option<PgnToken<'a>>,
+}
+
+impl<'a> PgnParser<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            lexer: PgnLexer::new(text),
+            peeked_token: None,
+        }
+    }
+
+    fn next_token(&mut self) -> Result<Option<PgnToken<'a>>, PgnError> {
+        match self.peeked_token.take() {
+            Some(token) => Ok(Some(token)),
+            None => self.lexer.next_token().transpose(),
+        }
+    }
+
+    fn peek_token(&mut self) -> Result<Option<&PgnToken<'a>>, PgnError> {
+        if self.peeked_token.is_none() {
+            self.peeked_token = self.lexer.next_token().transpose()?;
+        }
+        Ok(self.peeked_token.as_ref())
+    }
+
+    /// Returns *None* when there are no games left
+    pub fn read_game(&mut self) -> Option<Result<PgnGame, PgnError>> {
+        self.try_read_game().transpose()
+    }
+
+    fn try_read_game(&mut self) -> Result<Option<PgnGame>, PgnError> {
+        let tags = self.read_tags()?;
+        if tags.is_empty() && self.peek_token()?.is_none() {
+            return Ok(None);
+        }
+        self.read_movetext(tags).map(Some)
+    }
+
+    pub fn read_tags(&mut self) -> Result<Vec<(String, String)>, PgnError> {
+        let mut tags = Vec::new();
+        while let Some(PgnToken::Tag { .. }) = self.peek_token()? {
+            if let Some(PgnToken::Tag { name, value }) = self.next_token()? {
+                tags.push((name.to_string(), value));
+            }
+        }
+        Ok(tags)
+    }
+
+    /// Reads the moves and the result of the game the provided tags belong to
+    pub fn read_movetext(&mut self, tags: Vec<(String, String)>) -> Result<PgnGame, PgnError> {
+        let mut game = PgnGame::new();
+        for (name, value) in tags.iter() {
+            game.set_tag(name, value);
+        }
+
+        let mut board = game.get_starting_board()?;
+        let (starting_comments, moves) = self.read_line(&mut board, 0, false)?;
+        game.starting_comments = starting_comments;
+        game.moves = moves;
+
+        let mut result = game.get_tag("Result").unwrap_or("*").to_string();
+        if let Some(&PgnToken::GameResult(game_result)) = self.peek_token()? {
+            result = game_result.to_string();
+            self.next_token()?;
+        }
+        game.game_state = GameState::from_pgn_tags(&result, game.get_tag("Termination")).unwrap_or(GameState::InProgress);
+        game.set_tag("Result", game.game_state.get_pgn_result());
+
+        Ok(game)
+    }
+
+    /// Reads the moves of the main line or a variation, the *start_ply* is the number
+    /// of moves made in the game before the line starts
+    ///
+    /// Returns the comments written before the first move and the moves themselves.
+    /// The moves of a variation are undone after reading it
+    fn read_line(&mut self, board: &mut Board, start_ply: usize, is_variation: bool) -> Result<(Vec<String>, Vec<PgnMoveNode>), PgnError> {
+        let mut starting_comments = Vec::new();
+        let mut nodes: Vec<PgnMoveNode> = Vec::new();
+        let mut move_records: Vec<MoveRecord> = Vec::new();
+
+        loop {
+            match self.peek_token()? {
+                None | Some(PgnToken::Tag { .. }) | Some(PgnToken::GameResult(_)) => {
+                    if is_variation {
+                        return Err(PgnError::UnterminatedVariation);
+                    }
+                    break;
+                }
+                Some(PgnToken::VariationEnd) => {
+                    if !is_variation {
+                        return Err(PgnError::UnexpectedVariationEnd);
+                    }
+                    self.next_token()?;
+                    break;
+                }
+                _ => {}
+            }
+
+            match self.next_token()? {
+                Some(PgnToken::Comment(comment)) => match nodes.last_mut() {
+                    Some(node) => node.comments.push(normalize_comment(comment)),
+                    None => starting_comments.push(normalize_comment(comment)),
+                },
+                Some(PgnToken::Nag(nag)) => match nodes.last_mut() {
+                    Some(node) => node.nags.push(nag),
+                    None => return Err(PgnError::NagWithoutMove(nag)),
+                },
+                Some(PgnToken::VariationStart) => {
+                    let last_move_record = move_records.pop().ok_or(PgnError::VariationWithoutMove)?;
+                    let last_move = last_move_record.mov;
+                    board.undo_move(last_move_record);
+
+                    let (variation_comments, mut variation) = self.read_line(board, start_ply + nodes.len() - 1, true)?;
+                    if let Some(first_node) = variation.first_mut() {
+                        first_node.starting_comments = variation_comments;
+                    }
+
+                    move_records.push(board.make_move(last_move));
+                    if let Some(node) = nodes.last_mut() {
+                        node.variations.push(variation);
+                    }
+                }
+                Some(PgnToken::San(san)) => {
+                    let mov = board.parse_san(san).map_err(|error| PgnError::IllegalMove {
+                        ply: start_ply + nodes.len() + 1,
+                        san: san.to_string(),
+                        error,
+                    })?;
+                    move_records.push(board.make_move(mov));
+                    nodes.push(PgnMoveNode::new(mov));
+                }
+                _ => unreachable!("Line ending tokens are handled before"),
+            }
+        }
+
+        if is_variation {
+            for move_record in move_records.into_iter().rev() {
+                board.undo_move(move_record);
+            }
+        }
+
+        Ok((starting_comments, nodes))
+    }
+}
@@ -0,0 +1,145 @@
+use std::io::BufRead;
+
+use super::{pgn_error::PgnError, pgn_game::PgnGame, pgn_parser::PgnParser};
+
+/// Receives the games read by *PgnReader::visit_games*
+pub trait PgnVisitor {
+    /// Called with the tags of every game, the moves of the game are only read if it returns *true*
+    fn visit_tags(&mut self, tags: &[(String, String)]) -> bool;
+    fn visit_game(&mut self, game: PgnGame);
+    /// Called for every malformed game, the reading continues from the next game
+    fn visit_error(&mut self, error: PgnError);
+}
+
+/// Returns whether the line ends inside of a *{comment}*
+fn ends_in_comment(line: &str, is_in_comment: bool) -> bool {
+    let mut is_in_comment = is_in_comment;
+    for sym in line.chars() {
+        match (is_in_comment, sym) {
+            (true, '}') => is_in_comment = false,
+            (false, '{') => is_in_comment = true,
+            (false, ';') => break,
+            _ => {}
+        }
+    }
+    is_in_comment
+}
+
+/// Checks for the *Event* tag specifically, not the tags like *EventDate*
+fn is_event_tag_line(line: &str) -> bool {
+    line.strip_prefix("[Event")
+        .and_then(|rest| rest.chars().next())
+        .is_some_and(|sym| sym == '"' || sym.is_whitespace())
+}
+
+/// Reads the games of a PGN database one at a time, only a single game is kept in memory
+///
+/// A malformed game is reported as an error and skipped up to the start of the next game
+pub struct PgnReader<R: BufRead> {
+    reader: R,
+    /// The text of the game being read
+    game_text: String,
+    /// The first line of the next game, read while looking for the end of the current one
+    next_game_line: String,
+    line_buffer: Vec<u8>,
+    is_finished: bool,
+}
+
+impl<R: BufRead> PgnReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            game_text: String::new(),
+            next_game_line: String::new(),
+            line_buffer: Vec::new(),
+            is_finished: false,
+        }
+    }
+
+    /// Collects the lines of the next game into *game_text*, returns *false* if there are no games left
+    ///
+    /// A game ends at the first tag line after its movetext, or at the next *\[Event* tag.
+    /// Invalid UTF-8 is replaced so that a single broken byte doesn't stop the whole database
+    fn read_game_text(&mut self) -> Result<bool, PgnError> {
+        self.game_text.clear();
+        self.game_text.push_str(&self.next_game_line);
+        self.next_game_line.clear();
+
+        let mut has_event_tag = is_event_tag_line(&self.game_text);
+        let mut has_movetext = false;
+        let mut is_in_comment = false;
+
+        loop {
+            self.line_buffer.clear();
+            let length = self.reader.read_until(b'\n', &mut self.line_buffer)
+                .map_err(|error| PgnError::Io(error.to_string()))?;
+            if length == 0 {
+                break;
+            }
+
+            let line = String::from_utf8_lossy(&self.line_buffer);
+            let trimmed_line = line.trim_start();
+            let is_tag_line = trimmed_line.starts_with('[');
+            let is_event_line = is_event_tag_line(trimmed_line);
+
+            let is_next_game = (is_tag_line && has_movetext && (!is_in_comment || is_event_line))
+                || (is_event_line && has_event_tag);
+            if is_next_game {
+                self.next_game_line = line.into_owned();
+                break;
+            }
+
+            has_event_tag |= is_event_line;
+            if is_in_comment || !is_tag_line {
+                has_movetext |= !trimmed_line.is_empty() && !trimmed_line.starts_with('%');
+                is_in_comment = ends_in_comment(&line, is_in_comment);
+            }
+            self.game_text.push_str(&line);
+        }
+
+        Ok(!self.game_text.trim().is_empty())
+    }
+
+    /// Reads the next game whose tags pass the filter, the moves of the filtered out games aren't replayed
+    pub fn read_next_game(&mut self, mut filter: impl FnMut(&[(String, String)]) -> bool) -> Option<Result<PgnGame, PgnError>> {
+        while !self.is_finished {
+            match self.read_game_text() {
+                Ok(true) => {}
+                Ok(false) => {
+                    self.is_finished = true;
+                    return None;
+                }
+                Err(error) => {
+                    self.is_finished = true;
+                    return Some(Err(error));
+                }
+            }
+
+            let mut parser = PgnParser::new(&self.game_text);
+            match parser.read_tags() {
+                Ok(tags) if filter(&tags) => return Some(parser.read_movetext(tags)),
+                Ok(_) => {}
+                Err(error) => return Some(Err(error)),
+            }
+        }
+        None
+    }
+
+    /// Passes every game of the database to the visitor
+    pub fn visit_games(&mut self, visitor: &mut impl PgnVisitor) {
+        while let Some(game) = self.read_next_game(|tags| visitor.visit_tags(tags)) {
+            match game {
+                Ok(game) => visitor.visit_game(game),
+                Err(error) => visitor.visit_error(error),
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for PgnReader<R> {
+    type Item = Result<PgnGame, PgnError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_next_game(|_| true)
+    }
+}
@@ -0,0 +1,111 @@
+use crate::board::Board;
+
+use super::{pgn_error::PgnError, pgn_game::{self, PgnGame, PgnMoveNode}};
+
+/// The export format doesn't allow lines longer than this
+const MAX_LINE_LENGTH: usize = 80;
+
+fn escape_tag_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Splits the comment into words so that it can be wrapped between lines
+fn push_comment_tokens(tokens: &mut Vec<String>, comment: &str) {
+    let words: Vec<&str> = comment.split_whitespace().collect();
+    if words.is_empty() {
+        tokens.push(String::from("{}"));
+        return;
+    }
+
+    let last_word_index = words.len() - 1;
+    for (index, word) in words.iter().enumerate() {
+        let opening_brace = if index == 0 {"{"} else {""};
+        let closing_brace = if index == last_word_index {"}"} else {""};
+        tokens.push(format!("{}{}{}", opening_brace, word, closing_brace));
+    }
+}
+
+/// Converts the moves of the line into tokens, the board must be in the position the line starts from
+fn push_line_tokens(tokens: &mut Vec<String>, board: &mut Board, starting_comments: &[String], nodes: &[PgnMoveNode]) {
+    for comment in starting_comments.iter() {
+        push_comment_tokens(tokens, comment);
+    }
+
+    // black moves need the *N...* move number at the start of a line and after interruptions
+    let mut needs_move_number = true;
+    let mut move_records = Vec::with_capacity(nodes.len());
+
+    for node in nodes.iter() {
+        if board.is_white_to_move() {
+            tokens.push(format!("{}.", board.get_fullmove_number()));
+        } else if needs_move_number {
+            tokens.push(format!("{}...", board.get_fullmove_number()));
+        }
+
+        tokens.push(node.mov.to_san(board));
+        for nag in node.nags.iter() {
+            tokens.push(format!("${}", nag));
+        }
+        for comment in node.comments.iter() {
+            push_comment_tokens(tokens, comment);
+        }
+        needs_move_number = !node.comments.is_empty();
+
+        for variation in node.variations.iter().filter(|variation| !variation.is_empty()) {
+            let variation_start = tokens.len();
+            push_line_tokens(tokens, board, &variation[0].starting_comments, variation);
+            tokens[variation_start].insert(0, '(');
+            if let Some(last_token) = tokens.last_mut() {
+                last_token.push(')');
+            }
+            needs_move_number = true;
+        }
+
+        move_records.push(board.make_move(node.mov));
+    }
+
+    for move_record in move_records.into_iter().rev() {
+        board.undo_move(move_record);
+    }
+}
+
+impl PgnGame {
+    /// Writes the game in the PGN export format: the seven tag roster goes first,
+    /// the movetext uses the standard SAN and is wrapped at 80 characters
+    pub fn to_pgn(&self) -> Result<String, PgnError> {
+        let mut pgn = String::new();
+
+        for name in pgn_game::SEVEN_TAG_ROSTER.iter() {
+            let value = match *name {
+                "Result" => self.game_state.get_pgn_result(),
+                _ => self.get_tag(name).unwrap_or(pgn_game::get_roster_tag_default(name)),
+            };
+            pgn.push_str(&format!("[{} \"{}\"]\n", name, escape_tag_value(value)));
+        }
+        for (name, value) in self.get_tags().iter().filter(|(name, _)| !pgn_game::SEVEN_TAG_ROSTER.contains(&name.as_str())) {
+            pgn.push_str(&format!("[{} \"{}\"]\n", name, escape_tag_value(value)));
+        }
+        pgn.push('\n');
+
+        let mut tokens = Vec::new();
+        let mut board = self.get_starting_board()?;
+        push_line_tokens(&mut tokens, &mut board, &self.starting_comments, &self.moves);
+        tokens.push(self.game_state.get_pgn_result().to_string());
+
+        let mut line_length = 0;
+        for token in tokens.iter() {
+            if line_length > 0 && line_length + 1 + token.len() > MAX_LINE_LENGTH {
+                pgn.push('\n');
+                line_length = 0;
+            } else if line_length > 0 {
+                pgn.push(' ');
+                line_length += 1;
+            }
+            pgn.push_str(token);
+            line_length += token.len();
+        }
+        pgn.push('\n');
+
+        Ok(pgn)
+    }
+}
@@ -1,6 +1,6 @@
 use std::io::{self, Write};
 
-use crate::{board::Board, r#move::{Move, UciMove, UciMoveCreationResult}};
+use crate::{board::Board, r#move::{Move, UciMove}, search::{search_limits::SearchLimits, Searcher}};
 
 pub trait Player {
     fn get_move(&self, board: &Board) -> Move;
@@ -19,10 +19,10 @@ impl Player for PlayerHuman {
         io::stdin().read_line(&mut input).unwrap();
         let input = input.trim();
 
-        match UciMove::from_uci(input) {
-            UciMoveCreationResult::Success(mov) => Move::from_uci(mov, board),
-            UciMoveCreationResult::Failure => {
-                println!("Invalid move format. Please try again.");
+        match UciMove::try_from_uci(input) {
+            Ok(mov) => Move::from_uci(mov, board),
+            Err(error) => {
+                println!("Invalid move format ({}). Please try again.", error);
                 self.get_move(board)
             }
         }
@@ -30,11 +30,19 @@ impl Player for PlayerHuman {
 }
 
 pub struct PlayerAI {
+    search_limits: SearchLimits,
+}
 
+impl PlayerAI {
+    pub fn new(search_limits: SearchLimits) -> Self {
+        Self { search_limits }
+    }
 }
 
 impl Player for PlayerAI {
     fn get_move(&self, board: &Board) -> Move {
-        todo!()
+        let mut searcher = Searcher::new(self.search_limits);
+        let result = searcher.search(&mut board.clone());
+        result.best_move.expect("The bot was asked for a move when the game is over")
     }
 }
\ No newline at end of file
@@ -0,0 +1,53 @@
+use std::fmt::Display;
+
+use crate::{board_representation, castling, piece};
+
+/// Describes a single reason why a position can't occur in a legal game
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PositionViolation {
+    /// Each side must have exactly one king
+    WrongKingCount { color: usize, count: u32 },
+    PawnOnBackRank { square: usize },
+    /// The side that has just moved can't leave its king in check
+    SideNotToMoveInCheck,
+    /// The castling right is set but the king or the rook isn't on its start square
+    ///
+    /// The *castling_side* is either *castling::KING_SIDE* or *castling::QUEEN_SIDE*
+    InvalidCastlingRights { color: usize, castling_side: u8 },
+    /// There is no pawn that could have just made a double move to the provided square
+    InvalidEnPassantSquare { square: usize },
+    TooManyPieces { color: usize, count: u32 },
+    TooManyPawns { color: usize, count: u32 },
+    /// There are more extra queens, rooks, bishops and knights than the missing pawns could promote to
+    TooManyPromotedPieces { color: usize, count: u32 },
+}
+
+fn get_color_name(color: usize) -> &'static str {
+    if color == piece::WHITE {"white"} else {"black"}
+}
+
+impl Display for PositionViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::WrongKingCount { color, count } =>
+                write!(f, "{} has {} kings instead of 1", get_color_name(*color), count),
+            Self::PawnOnBackRank { square } =>
+                write!(f, "pawn on the back rank square {}", board_representation::get_square_name(*square)),
+            Self::SideNotToMoveInCheck =>
+                write!(f, "the side not to move is in check"),
+            Self::InvalidCastlingRights { color, castling_side } => write!(
+                f, "{} can't castle {} side",
+                get_color_name(*color),
+                if *castling_side == castling::KING_SIDE {"king"} else {"queen"}
+            ),
+            Self::InvalidEnPassantSquare { square } =>
+                write!(f, "invalid en passant square {}", board_representation::get_square_name(*square)),
+            Self::TooManyPieces { color, count } =>
+                write!(f, "{} has {} pieces, at most 16 are possible", get_color_name(*color), count),
+            Self::TooManyPawns { color, count } =>
+                write!(f, "{} has {} pawns, at most 8 are possible", get_color_name(*color), count),
+            Self::TooManyPromotedPieces { color, count } =>
+                write!(f, "{} has {} promoted pieces, which is more than the missing pawns", get_color_name(*color), count),
+        }
+    }
+}
@@ -9,11 +9,13 @@ pub mod square_magic;
 pub mod magic_bitboard_gen;
 pub mod magic_lookup_moves;
 pub mod magic_loader;
+pub mod zobrist_keys;
 
 use once_cell::sync::Lazy;
 
 use magic_lookup_table::MagicLookupTable;
 use square_data::SquareData;
+use zobrist_keys::ZobristKeys;
 
 use crate::piece;
 
@@ -21,6 +23,7 @@ pub static BITBOARD_DATA: Lazy<Box<bitboard_data::BitboardData>> = Lazy::new(bit
 pub static SQUARE_DATA: Lazy<Box<SquareData>> = Lazy::new(SquareData::new);
 pub static ROOK_MAGIC_LOOKUP_TABLE: Lazy<Box<MagicLookupTable>> = Lazy::new(|| MagicLookupTable::new(SLIDER_ROOK_INDEX));
 pub static BISHOP_MAGIC_LOOKUP_TABLE: Lazy<Box<MagicLookupTable>> = Lazy::new(|| MagicLookupTable::new(SLIDER_BISHOP_INDEX));
+pub static ZOBRIST_KEYS: Lazy<Box<ZobristKeys>> = Lazy::new(ZobristKeys::new);
 
 pub const SLIDER_TYPES: [usize; 2] = [piece::BISHOP, piece::ROOK];
 
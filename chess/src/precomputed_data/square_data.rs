@@ -51,6 +51,8 @@ pub struct SquareData {
     king_square_bbs: [u64; 64],
     /// Bitboards containing knight moves for an empty board for every square
     knight_square_bbs: [u64; 64],
+    /// Bitboards containing squares attacked by a pawn of each color from every square
+    pawn_attack_bbs: [[u64; 64]; 2],
     /// Bitboards containing lines from every square to every square (or zeros if no line is possible)
     square_to_square_line_bbs: [[u64; 64]; 64],
     /// Contains bitboards with all files, ranks and diagonals for each square on the board
@@ -68,12 +70,14 @@ impl SquareData {
             squares_to_edge,
             king_square_bbs: [0; 64],
             knight_square_bbs: [0; 64],
+            pawn_attack_bbs: [[0; 64]; 2],
             square_to_square_line_bbs: [[0; 64]; 64],
             files_ranks_diagonals: [[0; 64]; 4],
         };
 
         square_data.load_king_squares_bb();
         square_data.load_knight_squares_bb();
+        square_data.load_pawn_attacks_bb();
         square_data.load_lines_bb();
         square_data.load_files_ranks_diagonals();
 
@@ -102,6 +106,14 @@ impl SquareData {
             *self.knight_square_bbs.get_unchecked(square)
         }
     }
+    /// Returns bitboard with all squares a pawn of the provided color attacks from the provided square
+    pub fn get_bb_for_pawn_attacks(&self, color: usize, square: usize) -> u64 {
+        debug_assert!(color < 2, "Color out of bounds");
+        debug_assert!(square < 64, "Square index out of bounds");
+        unsafe {
+            *self.pawn_attack_bbs.get_unchecked(color).get_unchecked(square)
+        }
+    }
     /// Returns a bitboard containing all squares in line from start to target squares including
     /// 
     ///  Returns zero if no line was found
@@ -196,6 +208,26 @@ impl SquareData {
         }
     }
 
+    #[cold]
+    fn load_pawn_attacks_bb(&mut self) {
+        // white pawns attack diagonally upwards, black pawns diagonally downwards
+        let attack_direction_indices: [[usize; 2]; 2] = [[4, 5], [6, 7]];
+
+        for (color, direction_indices) in attack_direction_indices.iter().enumerate() {
+            for square in 0..64 {
+                let mut attacks_bb: u64 = 0;
+
+                for &direction_index in direction_indices {
+                    if self.get_squares_to_edge(square, direction_index) != 0 {
+                        attacks_bb |= 1 << ((square as isize + get_move_offset(direction_index)) as usize);
+                    }
+                }
+
+                self.pawn_attack_bbs[color][square] = attacks_bb;
+            }
+        }
+    }
+
     #[cold]
     fn load_lines_bb(&mut self) {
         for start_square in 0..64 {
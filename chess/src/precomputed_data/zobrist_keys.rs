@@ -0,0 +1,133 @@
+/// Seed for the key generation. The keys must stay the same between runs
+/// so that hashes saved to a file (like opening books) stay valid
+const ZOBRIST_SEED: u64 = 0x52_6F_62_65_4C_61_75_6E;
+
+/// The most pieces of a single type a pocket can hold (every pawn of both sides)
+pub const MAX_POCKET_COUNT: usize = 16;
+/// The number of checks that wins a Three-check game
+pub const MAX_CHECK_COUNT: usize = 3;
+
+/// Returns the next pseudo random number of the SplitMix64 sequence
+fn split_mix_64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E3779B97F4A7C15);
+    let mut result = *state;
+    result = (result ^ (result >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    result = (result ^ (result >> 27)).wrapping_mul(0x94D049BB133111EB);
+    result ^ (result >> 31)
+}
+
+/// Contains random keys for every single part of the position, which are
+/// xored together to get the hash of the position
+pub struct ZobristKeys {
+    /// Goes like *piece_square_keys\[color]\[piece_type]\[square]*, the keys at piece type 0 are unused
+    piece_square_keys: [[[u64; 64]; 7]; 2],
+    /// Gets xored in when black is to move
+    side_to_move_key: u64,
+    /// A key for every possible castling state of each color
+    castling_keys: [[u64; 4]; 2],
+    en_passant_file_keys: [u64; 8],
+    /// Goes like *pocket_keys\[color]\[piece_type]\[count - 1]*, a pocket with *n* pieces
+    /// of a type is hashed as the keys for every count from 1 to *n* xored together
+    pocket_keys: [[[u64; MAX_POCKET_COUNT]; 7]; 2],
+    /// Goes like *check_keys\[color]\[count - 1]*, the number of checks given by the color in Three-check
+    check_keys: [[u64; MAX_CHECK_COUNT]; 2],
+}
+
+impl ZobristKeys {
+    #[cold]
+    pub fn new() -> Box<Self> {
+        let mut state = ZOBRIST_SEED;
+        let mut zobrist_keys = Self {
+            piece_square_keys: [[[0; 64]; 7]; 2],
+            side_to_move_key: 0,
+            castling_keys: [[0; 4]; 2],
+            en_passant_file_keys: [0; 8],
+            pocket_keys: [[[0; MAX_POCKET_COUNT]; 7]; 2],
+            check_keys: [[0; MAX_CHECK_COUNT]; 2],
+        };
+
+        for color_keys in zobrist_keys.piece_square_keys.iter_mut() {
+            for piece_keys in color_keys.iter_mut().skip(1) {
+                for key in piece_keys.iter_mut() {
+                    *key = split_mix_64(&mut state);
+                }
+            }
+        }
+
+        zobrist_keys.side_to_move_key = split_mix_64(&mut state);
+
+        for color_keys in zobrist_keys.castling_keys.iter_mut() {
+            for key in color_keys.iter_mut() {
+                *key = split_mix_64(&mut state);
+            }
+        }
+
+        for key in zobrist_keys.en_passant_file_keys.iter_mut() {
+            *key = split_mix_64(&mut state);
+        }
+
+        // generated last so that the keys above stay the same as before the pockets were added
+        for color_keys in zobrist_keys.pocket_keys.iter_mut() {
+            for piece_keys in color_keys.iter_mut().skip(1) {
+                for key in piece_keys.iter_mut() {
+                    *key = split_mix_64(&mut state);
+                }
+            }
+        }
+
+        for color_keys in zobrist_keys.check_keys.iter_mut() {
+            for key in color_keys.iter_mut() {
+                *key = split_mix_64(&mut state);
+            }
+        }
+
+        Box::new(zobrist_keys)
+    }
+
+    pub fn get_piece_square_key(&self, color: usize, piece_type: usize, square: usize) -> u64 {
+        debug_assert!(color < 2, "Color out of bounds");
+        debug_assert!(piece_type < 7, "Piece type out of bounds");
+        debug_assert!(square < 64, "Square index out of bounds");
+        unsafe {
+            *self.piece_square_keys.get_unchecked(color).get_unchecked(piece_type).get_unchecked(square)
+        }
+    }
+
+    pub fn get_side_to_move_key(&self) -> u64 {
+        self.side_to_move_key
+    }
+
+    pub fn get_castling_key(&self, color: usize, castling_state: u8) -> u64 {
+        debug_assert!(color < 2, "Color out of bounds");
+        debug_assert!(castling_state < 4, "Castling state out of bounds");
+        unsafe {
+            *self.castling_keys.get_unchecked(color).get_unchecked(castling_state as usize)
+        }
+    }
+
+    pub fn get_en_passant_file_key(&self, file: usize) -> u64 {
+        debug_assert!(file < 8, "File out of bounds");
+        unsafe {
+            *self.en_passant_file_keys.get_unchecked(file)
+        }
+    }
+
+    /// Returns the key of the *count*-th piece of the type in the pocket, the count starts from 1
+    pub fn get_pocket_key(&self, color: usize, piece_type: usize, count: usize) -> u64 {
+        debug_assert!(color < 2, "Color out of bounds");
+        debug_assert!(piece_type < 7, "Piece type out of bounds");
+        debug_assert!((1..=MAX_POCKET_COUNT).contains(&count), "Pocket count out of bounds");
+        unsafe {
+            *self.pocket_keys.get_unchecked(color).get_unchecked(piece_type).get_unchecked(count - 1)
+        }
+    }
+
+    /// Returns the key of the provided number of checks given by the color, the count starts from 1
+    pub fn get_check_key(&self, color: usize, count: usize) -> u64 {
+        debug_assert!(color < 2, "Color out of bounds");
+        debug_assert!((1..=MAX_CHECK_COUNT).contains(&count), "Check count out of bounds");
+        unsafe {
+            *self.check_keys.get_unchecked(color).get_unchecked(count - 1)
+        }
+    }
+}
\ No newline at end of file
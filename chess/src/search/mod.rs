@@ -0,0 +1,412 @@
+/* Negamax alpha-beta search with iterative deepening. Every iteration goes one ply deeper and tries the
+principal variation of the previous one first, the quiescence search resolves the captures at the leaves.
+The scores are from the side to move's point of view, the mates are scored by their distance from the root */
+use std::time::Instant;
+
+use core::game::GameState;
+
+use crate::{bitboards, board::Board, r#move::{get_promotion_piece, Move, MoveType}, move_generation::{move_gen::{self, GenType, MAX_CRAZYHOUSE_MOVES_PER_POS, MAX_MOVES_PER_POS}, move_list::MoveList}, piece, see, variant::Variant};
+
+pub mod pv_table;
+pub mod search_limits;
+
+use pv_table::PvTable;
+use search_limits::SearchLimits;
+
+/// The deepest ply the search can reach, the quiescence search included
+pub const MAX_PLY: usize = 128;
+/// Score of checkmating on the root, the mates further away score less by one per ply
+pub const MATE_SCORE: i32 = 32000;
+/// Bound no score can reach, used as the initial window
+pub const INFINITE_SCORE: i32 = MATE_SCORE + 1;
+/// Every score above it (or below its negation) is a forced mate
+const MATE_BOUND: i32 = MATE_SCORE - MAX_PLY as i32;
+
+/// How many nodes are visited between checking the time
+const TIME_CHECK_INTERVAL: u64 = 1024;
+
+const PV_MOVE_SCORE: i32 = 1_000_000;
+const GOOD_CAPTURE_SCORE: i32 = 100_000;
+const FIRST_KILLER_SCORE: i32 = 90_000;
+const SECOND_KILLER_SCORE: i32 = 80_000;
+const BAD_CAPTURE_SCORE: i32 = -100_000;
+
+/// Returns the number of moves to the mate, negative if the side to move gets mated,
+/// *None* if the score is not a mate score
+pub fn get_mate_distance(score: i32) -> Option<i32> {
+    if score > MATE_BOUND {
+        Some((MATE_SCORE - score + 1) / 2)
+    } else if score < -MATE_BOUND {
+        Some(-(MATE_SCORE + score) / 2)
+    } else {
+        None
+    }
+}
+
+/// Formats the score like the UCI *score* info, *cp 35* or *mate -2*
+pub fn format_score(score: i32) -> String {
+    match get_mate_distance(score) {
+        Some(mate_distance) => format!("mate {}", mate_distance),
+        None => format!("cp {}", score),
+    }
+}
+
+fn is_capture(board: &Board, mov: &Move) -> bool {
+    mov.is_en_passant()
+    || (!mov.is_drop() && board.get_all_occupied_squares_for_color(board.get_opposite_color())
+        & bitboards::get_bit_from_square(mov.get_target_square()) != 0)
+}
+
+/// The outcome of a search, taken from the last iteration that was searched completely
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchResult {
+    /// *None* only if the side to move has no legal moves or the game is already over
+    pub best_move: Option<Move>,
+    pub score: i32,
+    pub depth: u8,
+    pub nodes: u64,
+    /// The principal variation, starting with the best move
+    pub pv: Vec<Move>,
+}
+
+pub struct Searcher {
+    limits: SearchLimits,
+    start_time: Instant,
+    nodes: u64,
+    /// Set once a limit is reached, the unfinished iteration is thrown away then
+    stopped: bool,
+    /// Limits are only checked after the first iteration, so there is always a move to play
+    can_stop: bool,
+    pv_table: PvTable,
+    /// The principal variation of the previous iteration, searched first in the next one
+    previous_pv: Vec<Move>,
+    /// Two quiet moves per ply that caused a beta cutoff, goes like *killer_moves\[ply]\[slot]*
+    killer_moves: [[Move; 2]; MAX_PLY],
+    /// How often the quiet moves caused a cutoff, goes like *history_scores\[color]\[start_square]\[target_square]*
+    history_scores: Box<[[[i32; 64]; 64]; 2]>,
+}
+
+impl Searcher {
+    pub fn new(limits: SearchLimits) -> Self {
+        Self {
+            limits,
+            start_time: Instant::now(),
+            nodes: 0,
+            stopped: false,
+            can_stop: false,
+            pv_table: PvTable::new(),
+            previous_pv: Vec::new(),
+            killer_moves: [[Move::from_packed(0); 2]; MAX_PLY],
+            history_scores: Box::new([[[0; 64]; 64]; 2]),
+        }
+    }
+
+    /// Searches the position with iterative deepening until a limit is reached, the board is restored afterwards
+    pub fn search(&mut self, board: &mut Board) -> SearchResult {
+        self.start_time = Instant::now();
+        self.nodes = 0;
+        self.stopped = false;
+        self.can_stop = false;
+        self.previous_pv.clear();
+        self.killer_moves = [[Move::from_packed(0); 2]; MAX_PLY];
+        *self.history_scores = [[[0; 64]; 64]; 2];
+
+        if board.get_variant().has_pockets() {
+            self.iterative_deepening::<MAX_CRAZYHOUSE_MOVES_PER_POS>(board)
+        } else {
+            self.iterative_deepening::<MAX_MOVES_PER_POS>(board)
+        }
+    }
+
+    /// Runs the search with the move lists long enough for the variant, only Crazyhouse needs the longer ones
+    fn iterative_deepening<const N: usize>(&mut self, board: &mut Board) -> SearchResult {
+        let mut result = SearchResult {
+            best_move: None,
+            score: 0,
+            depth: 0,
+            nodes: 0,
+            pv: Vec::new(),
+        };
+
+        let mut root_moves = MoveList::<N>::new();
+        move_gen::generate_moves(&mut root_moves, board);
+        if root_moves.is_empty() || board.get_variant_game_state().is_some() {
+            result.score = self.get_variant_game_state_score(board, 0)
+                .unwrap_or_else(|| self.get_no_moves_score(board, 0, move_gen::is_in_check(board)));
+            return result;
+        }
+
+        let mut move_buffer: Vec<MoveList<N>> = (0..MAX_PLY)
+            .map(|_| MoveList::new())
+            .collect();
+        let max_depth = self.limits.depth.unwrap_or(MAX_PLY as u8 - 1).clamp(1, MAX_PLY as u8 - 1);
+
+        for depth in 1..=max_depth {
+            let score = self.negamax(board, depth as i32, 0, -INFINITE_SCORE, INFINITE_SCORE, &mut move_buffer);
+            if self.stopped {
+                break;
+            }
+
+            self.previous_pv = self.pv_table.get_line(0).to_vec();
+            result = SearchResult {
+                best_move: self.previous_pv.first().copied(),
+                score,
+                depth,
+                nodes: self.nodes,
+                pv: self.previous_pv.clone(),
+            };
+            self.can_stop = true;
+
+            if core::config::DO_TERMINAL_OUTPUT {
+                let pv: Vec<String> = result.pv.iter().map(|mov| mov.to_string()).collect();
+                println!("depth {} score {} nodes {} time {:?} pv {}", depth, format_score(score), self.nodes, self.start_time.elapsed(), pv.join(" "));
+            }
+
+            // a deeper search can't find a shorter mate
+            if get_mate_distance(score).is_some_and(|mate_distance| mate_distance.unsigned_abs() * 2 <= depth as u32) {
+                break;
+            }
+        }
+
+        result.nodes = self.nodes;
+        result
+    }
+
+    /// Returns true once a limit is reached, the time is checked only every *TIME_CHECK_INTERVAL* nodes
+    fn should_stop(&mut self) -> bool {
+        if self.stopped {
+            return true;
+        }
+        if !self.can_stop {
+            return false;
+        }
+
+        let nodes_exceeded = self.limits.nodes.is_some_and(|max_nodes| self.nodes >= max_nodes);
+        let time_exceeded = self.nodes.is_multiple_of(TIME_CHECK_INTERVAL)
+            && self.limits.time.is_some_and(|max_time| self.start_time.elapsed() >= max_time);
+
+        self.stopped = nodes_exceeded || time_exceeded;
+        self.stopped
+    }
+
+    /// Returns the score of the position if the game was decided by a rule of the variant, like the third check
+    ///
+    /// The ply is the distance from the root, so the closer wins score higher
+    fn get_variant_game_state_score(&self, board: &Board, ply: usize) -> Option<i32> {
+        let won_score = MATE_SCORE - ply as i32;
+        let winner = match board.get_variant_game_state()? {
+            GameState::WhiteWon(_) => piece::WHITE,
+            GameState::BlackWon(_) => piece::BLACK,
+            _ => return Some(0),
+        };
+        Some(if winner == board.get_current_color() {won_score} else {-won_score})
+    }
+
+    /// Returns the score of the position without legal moves: checkmate, stalemate or the Antichess win
+    fn get_no_moves_score(&self, board: &Board, ply: usize, is_in_check: bool) -> i32 {
+        if board.get_variant() == Variant::Antichess {
+            // the side that can't move wins in Antichess
+            MATE_SCORE - ply as i32
+        } else if is_in_check {
+            -MATE_SCORE + ply as i32
+        } else {
+            0
+        }
+    }
+
+    /// Returns true if the position is drawn by the repetition, the fifty move rule or the insufficient material,
+    /// a single repetition is enough since the side that could avoid it would have done so
+    fn is_draw(&self, board: &Board) -> bool {
+        board.is_fifty_move_rule() || board.get_repetition_count() >= 2 || board.has_insufficient_material()
+    }
+
+    fn negamax<const N: usize>(&mut self, board: &mut Board, mut depth: i32, ply: usize, mut alpha: i32, mut beta: i32, move_buffer: &mut [MoveList<N>]) -> i32 {
+        self.pv_table.clear_ply(ply);
+        self.nodes += 1;
+        if self.should_stop() {
+            return 0;
+        }
+
+        if ply > 0 {
+            if let Some(game_state_score) = self.get_variant_game_state_score(board, ply) {
+                return game_state_score;
+            }
+            if self.is_draw(board) {
+                return 0;
+            }
+
+            // no line from here can beat a mate already found closer to the root
+            alpha = alpha.max(-MATE_SCORE + ply as i32);
+            beta = beta.min(MATE_SCORE - ply as i32 - 1);
+            if alpha >= beta {
+                return alpha;
+            }
+        }
+
+        if ply >= MAX_PLY - 1 {
+            return board.evaluate();
+        }
+
+        let is_in_check = move_gen::is_in_check(board);
+        if is_in_check {
+            depth += 1;
+        }
+        if depth <= 0 {
+            return self.quiescence(board, ply, alpha, beta, move_buffer);
+        }
+
+        let (current_moves, remaining_buffer) = move_buffer.split_at_mut(1);
+        let moves = unsafe { current_moves.get_unchecked_mut(0) };
+        moves.clear();
+        move_gen::generate_moves(moves, board);
+
+        if moves.is_empty() {
+            return self.get_no_moves_score(board, ply, is_in_check);
+        }
+
+        self.score_moves(moves, board, ply);
+
+        let current_color = board.get_current_color();
+        let mut best_score = -INFINITE_SCORE;
+        for index in 0..moves.len() {
+            let mov = moves.pick_best(index);
+            let is_quiet = !is_capture(board, &mov) && !mov.is_promotion();
+
+            let move_record = board.make_move(mov);
+            let score = -self.negamax(board, depth - 1, ply + 1, -beta, -alpha, remaining_buffer);
+            board.undo_move(move_record);
+
+            if self.stopped {
+                return 0;
+            }
+
+            if score > best_score {
+                best_score = score;
+            }
+            if score > alpha {
+                alpha = score;
+                self.pv_table.update(ply, mov);
+            }
+            if alpha >= beta {
+                if is_quiet {
+                    self.store_killer_move(ply, mov);
+                    self.history_scores[current_color][mov.get_start_square()][mov.get_target_square()] += depth * depth;
+                }
+                break;
+            }
+        }
+
+        best_score
+    }
+
+    /// Searches only the captures and promotions until the position is quiet, so the evaluation is not
+    /// taken in the middle of an exchange. Every move is searched when in check, the mates are found that way
+    fn quiescence<const N: usize>(&mut self, board: &mut Board, ply: usize, mut alpha: i32, beta: i32, move_buffer: &mut [MoveList<N>]) -> i32 {
+        self.pv_table.clear_ply(ply);
+        self.nodes += 1;
+        if self.should_stop() {
+            return 0;
+        }
+
+        if let Some(game_state_score) = self.get_variant_game_state_score(board, ply) {
+            return game_state_score;
+        }
+        if ply >= MAX_PLY - 1 {
+            return board.evaluate();
+        }
+
+        let is_in_check = move_gen::is_in_check(board);
+        let (current_moves, remaining_buffer) = move_buffer.split_at_mut(1);
+        let moves = unsafe { current_moves.get_unchecked_mut(0) };
+        moves.clear();
+        move_gen::generate_staged_moves(moves, board, if is_in_check {GenType::Evasions} else {GenType::Captures});
+
+        let mut best_score = -INFINITE_SCORE;
+        // the side to move doesn't have to capture and can keep the current position instead
+        if !is_in_check {
+            best_score = board.evaluate();
+            if best_score >= beta {
+                return best_score;
+            }
+            alpha = alpha.max(best_score);
+        }
+
+        if moves.is_empty() {
+            return if is_in_check {self.get_no_moves_score(board, ply, is_in_check)} else {best_score};
+        }
+
+        self.score_moves(moves, board, ply);
+
+        for index in 0..moves.len() {
+            let mov = moves.pick_best(index);
+
+            // the captures losing material can't improve the score, except when the variant rules change the exchanges
+            if !is_in_check && moves.get_score(index) < 0 && matches!(board.get_variant(), Variant::Standard | Variant::ThreeCheck | Variant::KingOfTheHill) {
+                break;
+            }
+
+            let move_record = board.make_move(mov);
+            let score = -self.quiescence(board, ply + 1, -beta, -alpha, remaining_buffer);
+            board.undo_move(move_record);
+
+            if self.stopped {
+                return 0;
+            }
+
+            if score > best_score {
+                best_score = score;
+            }
+            if score > alpha {
+                alpha = score;
+                self.pv_table.update(ply, mov);
+            }
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        best_score
+    }
+
+    /// Orders the moves: the move of the previous principal variation, the captures winning material
+    /// and the promotions, the killer moves, the quiet moves by their history and the losing captures last
+    fn score_moves<const N: usize>(&self, moves: &mut MoveList<N>, board: &Board, ply: usize) {
+        let current_color = board.get_current_color();
+        let pv_move = self.previous_pv.get(ply).copied();
+        let killer_moves = self.killer_moves[ply];
+
+        moves.score_moves(|mov| {
+            if Some(mov) == pv_move {
+                return PV_MOVE_SCORE;
+            }
+
+            if is_capture(board, &mov) || mov.is_promotion() {
+                let see_score = board.see(&mov);
+                // the most valuable victims first, taken by the least valuable attackers
+                let victim_value = if is_capture(board, &mov) {see::get_see_piece_value(board.get_piece_on_square(mov.get_capture_square()).1)} else {0};
+                let promotion_value = if mov.is_promotion() {see::get_see_piece_value(get_promotion_piece(mov.get_move_type()))} else {0};
+                let attacker_value = see::get_see_piece_value(board.get_piece_on_square(mov.get_start_square()).1) / 100;
+                let mvv_lva_score = (victim_value + promotion_value) * 10 - attacker_value;
+                return if see_score >= 0 {GOOD_CAPTURE_SCORE + mvv_lva_score} else {BAD_CAPTURE_SCORE + mvv_lva_score};
+            }
+
+            if mov == killer_moves[0] {
+                FIRST_KILLER_SCORE
+            } else if mov == killer_moves[1] {
+                SECOND_KILLER_SCORE
+            } else if let MoveType::Drop(_) = mov.get_move_type() {
+                0
+            } else {
+                self.history_scores[current_color][mov.get_start_square()][mov.get_target_square()].min(SECOND_KILLER_SCORE - 1)
+            }
+        });
+    }
+
+    fn store_killer_move(&mut self, ply: usize, mov: Move) {
+        let killer_moves = &mut self.killer_moves[ply];
+        if killer_moves[0] != mov {
+            killer_moves[1] = killer_moves[0];
+            killer_moves[0] = mov;
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use crate::r#move::Move;
+
+use super::MAX_PLY;
+
+/// Triangular table of the principal variations, the row of every ply holds the best line found
+/// from that ply on, so the line of the root is built up as the search returns
+#[derive(Clone)]
+pub struct PvTable {
+    moves: Box<[[Move; MAX_PLY]; MAX_PLY]>,
+    lengths: [usize; MAX_PLY],
+}
+
+impl PvTable {
+    pub fn new() -> Self {
+        Self {
+            moves: Box::new([[Move::from_packed(0); MAX_PLY]; MAX_PLY]),
+            lengths: [0; MAX_PLY],
+        }
+    }
+
+    /// Empties the line of the ply, has to be called when the search enters a node
+    pub fn clear_ply(&mut self, ply: usize) {
+        debug_assert!(ply < MAX_PLY, "Ply out of bounds");
+        unsafe { *self.lengths.get_unchecked_mut(ply) = 0; }
+    }
+
+    /// Sets the line of the ply to the move followed by the line of the next ply
+    pub fn update(&mut self, ply: usize, mov: Move) {
+        debug_assert!(ply < MAX_PLY, "Ply out of bounds");
+        let child_length = if ply + 1 < MAX_PLY {self.lengths[ply + 1]} else {0};
+
+        let (current_rows, child_rows) = self.moves.split_at_mut(ply + 1);
+        let current_row = &mut current_rows[ply];
+        current_row[0] = mov;
+        if child_length > 0 {
+            current_row[1..=child_length].copy_from_slice(&child_rows[0][..child_length]);
+        }
+        self.lengths[ply] = child_length + 1;
+    }
+
+    /// Returns the best line found from the ply on
+    pub fn get_line(&self, ply: usize) -> &[Move] {
+        debug_assert!(ply < MAX_PLY, "Ply out of bounds");
+        &self.moves[ply][..self.lengths[ply]]
+    }
+}
+
+impl Default for PvTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
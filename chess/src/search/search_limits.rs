@@ -0,0 +1,32 @@
+use std::time::Duration;
+
+/// Tells the search when to stop, the search ends as soon as any of the set limits is reached
+///
+/// With no limits set the search goes on until *MAX_PLY* or a forced mate is found
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SearchLimits {
+    /// The deepest iteration of the iterative deepening
+    pub depth: Option<u8>,
+    /// The number of positions visited, the quiescence search included
+    pub nodes: Option<u64>,
+    pub time: Option<Duration>,
+}
+
+impl SearchLimits {
+    pub fn depth(depth: u8) -> Self {
+        Self { depth: Some(depth), ..Self::default() }
+    }
+
+    pub fn nodes(nodes: u64) -> Self {
+        Self { nodes: Some(nodes), ..Self::default() }
+    }
+
+    pub fn time(time: Duration) -> Self {
+        Self { time: Some(time), ..Self::default() }
+    }
+
+    /// Returns true if only the forced mates and *MAX_PLY* can stop the search
+    pub fn is_infinite(&self) -> bool {
+        self.depth.is_none() && self.nodes.is_none() && self.time.is_none()
+    }
+}
@@ -0,0 +1,152 @@
+/* Static Exchange Evaluation. Plays out every capture on the target square of a move, always with the least
+valuable attacker, and lets each side stop capturing whenever it is better for them. The sliders behind the
+capturing pieces join in as x-rays. The pins and the checks are not taken into account, the variant rules neither */
+use crate::{bitboards, board::Board, r#move::{get_promotion_piece, Move, MoveType}, piece, precomputed_data};
+
+/// Values of the pieces used by the exchange evaluation indexed by the piece type,
+/// the king is worth more than everything else together so it is never traded
+const SEE_PIECE_VALUES: [i32; 7] = [0, 20000, 100, 320, 330, 500, 900];
+
+pub fn get_see_piece_value(piece_type: usize) -> i32 {
+    debug_assert!(piece_type < 7, "Piece type out of bounds");
+    unsafe { *SEE_PIECE_VALUES.get_unchecked(piece_type) }
+}
+
+/// The attacker types in the order they are used for the captures
+const ATTACKER_ORDER: [usize; 6] = [piece::PAWN, piece::KNIGHT, piece::BISHOP, piece::ROOK, piece::QUEEN, piece::KING];
+
+impl Board {
+    /// Returns the sliders seen through the squares emptied by the exchange
+    fn get_x_ray_attackers_bb(&self, square: usize, occupancy: u64) -> u64 {
+        let queen_bb = self.get_piece_type_bitboard(piece::QUEEN);
+        ((precomputed_data::BISHOP_MAGIC_LOOKUP_TABLE.get_pseudo_legal_move_bb(square, occupancy)
+            & (self.get_piece_type_bitboard(piece::BISHOP) | queen_bb))
+        | (precomputed_data::ROOK_MAGIC_LOOKUP_TABLE.get_pseudo_legal_move_bb(square, occupancy)
+            & (self.get_piece_type_bitboard(piece::ROOK) | queen_bb)))
+        & occupancy
+    }
+
+    /// Returns the least valuable piece of the attackers and its type
+    fn get_least_valuable_attacker(&self, attackers_bb: u64, color: usize) -> Option<(u64, usize)> {
+        ATTACKER_ORDER.iter().find_map(|&piece_type| {
+            let piece_attackers_bb = attackers_bb & self.get_piece_bitboard(color, piece_type);
+            (piece_attackers_bb != 0).then(|| (piece_attackers_bb & piece_attackers_bb.wrapping_neg(), piece_type))
+        })
+    }
+
+    /// Returns the material gained by the first capture and the value of the piece standing on the
+    /// target square afterwards, the promotions gain the difference between the new piece and the pawn
+    fn get_first_capture_values(&self, mov: &Move) -> (i32, i32) {
+        let move_type = mov.get_move_type();
+        let captured_value = get_see_piece_value(self.get_piece_on_square(mov.get_capture_square()).1);
+
+        if mov.is_promotion() {
+            let promotion_value = get_see_piece_value(get_promotion_piece(move_type));
+            return (captured_value + promotion_value - get_see_piece_value(piece::PAWN), promotion_value);
+        }
+        (captured_value, get_see_piece_value(self.get_piece_on_square(mov.get_start_square()).1))
+    }
+
+    /// Returns the occupancy after the first capture and the attackers of the target square in it
+    fn get_exchange_start(&self, mov: &Move) -> (u64, u64) {
+        let occupancy = (self.get_all_occupied_squares()
+            & !bitboards::get_bit_from_square(mov.get_start_square())
+            & !bitboards::get_bit_from_square(mov.get_capture_square()))
+            | bitboards::get_bit_from_square(mov.get_target_square());
+        // the piece that has just captured is not an attacker of its own square
+        let attackers_bb = self.all_attackers_to(mov.get_target_square(), occupancy) & !bitboards::get_bit_from_square(mov.get_target_square());
+        (occupancy, attackers_bb)
+    }
+
+    /// Returns the material the side to move wins (or loses if negative) by the exchange on the target square
+    /// started with the move, the castling and the drops are worth nothing
+    pub fn see(&self, mov: &Move) -> i32 {
+        if mov.is_castling() || matches!(mov.get_move_type(), MoveType::Drop(_)) {
+            return 0;
+        }
+
+        let target_square = mov.get_target_square();
+        let (first_gain, mut piece_on_square_value) = self.get_first_capture_values(mov);
+        let (mut occupancy, mut attackers_bb) = self.get_exchange_start(mov);
+
+        // gains[depth] is the material won by the side making the capture at that depth if the exchange stops after it
+        let mut gains = [0; 33];
+        gains[0] = first_gain;
+        let mut depth = 0;
+        let mut color = self.get_opposite_color();
+
+        while let Some((attacker_bb, piece_type)) = self.get_least_valuable_attacker(attackers_bb, color) {
+            let occupancy_after_capture = occupancy & !attacker_bb;
+            let attackers_after_capture_bb = (attackers_bb & !attacker_bb) | self.get_x_ray_attackers_bb(target_square, occupancy_after_capture);
+
+            // the king can't capture a defended piece
+            if piece_type == piece::KING && attackers_after_capture_bb & self.get_all_occupied_squares_for_color(1 - color) != 0 {
+                break;
+            }
+
+            depth += 1;
+            gains[depth] = piece_on_square_value - gains[depth - 1];
+            piece_on_square_value = get_see_piece_value(piece_type);
+            occupancy = occupancy_after_capture;
+            attackers_bb = attackers_after_capture_bb;
+            color = 1 - color;
+        }
+
+        // every side stops capturing once it isn't worth it anymore
+        while depth > 0 {
+            gains[depth - 1] = -(-gains[depth - 1]).max(gains[depth]);
+            depth -= 1;
+        }
+        gains[0]
+    }
+
+    /// Returns true if the exchange started with the move wins at least the threshold, same as *see* >= threshold
+    /// but it stops as soon as the result is known
+    pub fn see_ge(&self, mov: &Move, threshold: i32) -> bool {
+        if mov.is_castling() || matches!(mov.get_move_type(), MoveType::Drop(_)) {
+            return 0 >= threshold;
+        }
+
+        let target_square = mov.get_target_square();
+        let (first_gain, piece_on_square_value) = self.get_first_capture_values(mov);
+
+        // the balance is the result for the side to move if the exchange stops here
+        let mut balance = first_gain - threshold;
+        if balance < 0 {
+            return false;
+        }
+        balance = piece_on_square_value - balance;
+        if balance <= 0 {
+            return true; // even losing the capturing piece keeps the threshold
+        }
+
+        let (mut occupancy, mut attackers_bb) = self.get_exchange_start(mov);
+        let mut color = self.get_current_color();
+        let mut result = true;
+
+        loop {
+            color = 1 - color;
+            let Some((attacker_bb, piece_type)) = self.get_least_valuable_attacker(attackers_bb, color) else {
+                break;
+            };
+
+            if piece_type == piece::KING {
+                // the king captures only if nothing can take it back
+                let occupancy_after_capture = occupancy & !attacker_bb;
+                let attackers_after_capture_bb = (attackers_bb & !attacker_bb) | self.get_x_ray_attackers_bb(target_square, occupancy_after_capture);
+                return if attackers_after_capture_bb & self.get_all_occupied_squares_for_color(1 - color) != 0 {result} else {!result};
+            }
+
+            result = !result;
+            balance = get_see_piece_value(piece_type) - balance;
+            if balance < result as i32 {
+                break;
+            }
+
+            occupancy &= !attacker_bb;
+            attackers_bb = (attackers_bb & !attacker_bb) | self.get_x_ray_attackers_bb(target_square, occupancy);
+        }
+
+        result
+    }
+}
@@ -0,0 +1,62 @@
+/// Rule set the game is played by
+///
+/// Chess960 only changes the starting position and the castling, so it can be combined with any
+/// variant and is set separately with *Board::set_chess960*
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Variant {
+    #[default]
+    Standard,
+    /// Captured pieces go to the pocket of the capturing side and can be dropped back onto the board
+    Crazyhouse,
+    /// Every capture explodes the capturing piece and the pieces around the capture square except pawns,
+    /// the game is won by exploding the enemy king
+    Atomic,
+    /// Giving the third check wins the game
+    ThreeCheck,
+    /// Bringing the king to one of the four central squares wins the game
+    KingOfTheHill,
+    /// Also known as the losing chess, captures are mandatory and the side that loses all its pieces
+    /// or has no moves wins, the king is an ordinary piece
+    Antichess,
+}
+
+impl Variant {
+    /// Returns true if captured pieces are kept in pockets and can be dropped
+    pub fn has_pockets(&self) -> bool {
+        *self == Variant::Crazyhouse
+    }
+
+    /// Returns false if the king is an ordinary piece that can be captured, so there are no checks
+    pub fn is_king_royal(&self) -> bool {
+        *self != Variant::Antichess
+    }
+
+    pub fn has_castling(&self) -> bool {
+        *self != Variant::Antichess
+    }
+
+    /// Returns the name of the variant used in the PGN *Variant* tag
+    pub fn get_pgn_name(&self) -> &'static str {
+        match self {
+            Variant::Standard => "Standard",
+            Variant::Crazyhouse => "Crazyhouse",
+            Variant::Atomic => "Atomic",
+            Variant::ThreeCheck => "Three-check",
+            Variant::KingOfTheHill => "King of the Hill",
+            Variant::Antichess => "Antichess",
+        }
+    }
+
+    /// Accepts the value of the PGN *Variant* tag, the case is ignored
+    pub fn from_pgn_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "standard" | "chess" | "chess960" | "chess 960" | "fischerandom" | "fischer random" | "fischerrandom" => Some(Variant::Standard),
+            "crazyhouse" => Some(Variant::Crazyhouse),
+            "atomic" => Some(Variant::Atomic),
+            "three-check" | "threecheck" | "3check" | "3-check" => Some(Variant::ThreeCheck),
+            "king of the hill" | "kingofthehill" | "koth" => Some(Variant::KingOfTheHill),
+            "antichess" | "losing chess" | "losingchess" | "giveaway" => Some(Variant::Antichess),
+            _ => None,
+        }
+    }
+}
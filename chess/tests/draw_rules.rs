@@ -0,0 +1,23 @@
+use chess::{board::Board, variant::Variant};
+use core::game::Termination;
+
+#[test]
+fn lone_minor_piece_is_insufficient_material_in_standard_chess() {
+    let board = Board::from_fen("8/8/8/8/8/8/8/KB5k w - -");
+    assert!(board.has_insufficient_material());
+    assert_eq!(board.get_draw_reason(), Some(Termination::InsufficientMaterial));
+}
+
+#[test]
+fn lone_minor_piece_can_still_give_three_checks() {
+    for fen in ["8/8/8/8/8/8/8/KB5k w - -", "8/8/8/8/8/8/8/KN5k w - -"] {
+        let mut board = Board::from_fen(fen);
+        board.set_variant(Variant::ThreeCheck);
+        assert!(!board.has_insufficient_material(), "{} is not dead in Three-check", fen);
+        assert_eq!(board.get_draw_reason(), None);
+    }
+
+    let mut board = Board::from_fen("8/8/8/8/8/8/8/K6k w - -");
+    board.set_variant(Variant::ThreeCheck);
+    assert!(board.has_insufficient_material(), "Bare kings can't give any check");
+}
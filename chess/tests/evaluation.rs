@@ -0,0 +1,14 @@
+use chess::{config, epd};
+
+#[test]
+fn evaluation_is_the_same_for_color_flipped_positions() {
+    let records = epd::load_epd_file(config::get_test_suite_path(config::PERFT_SUITE_FILE)).expect("Failed to load the perft suite");
+    assert!(!records.is_empty(), "The perft suite is empty");
+
+    for record in records.iter() {
+        let board = record.get_board();
+        let flipped_board = board.get_color_flipped();
+        assert_eq!(flipped_board.get_color_flipped().to_fen(), board.to_fen(), "Flipping twice changed the position");
+        assert_eq!(board.evaluate(), flipped_board.evaluate(), "Evaluation differs between {} and {}", board.to_fen(), flipped_board.to_fen());
+    }
+}
@@ -0,0 +1,19 @@
+use chess::{config, epd, search::{search_limits::SearchLimits, Searcher}};
+
+/// Depth of the search on every position, enough for the mates in 2 of the suite
+const MATE_SEARCH_DEPTH: u8 = 4;
+
+#[test]
+fn search_finds_the_best_move_of_the_mate_suite() {
+    let records = epd::load_epd_file(config::get_test_suite_path(config::MATE_SUITE_FILE)).expect("Failed to load the mate suite");
+    assert!(!records.is_empty(), "The mate suite is empty");
+
+    for record in records.iter() {
+        let best_moves = record.get_best_moves().expect("Every record of the mate suite has a best move");
+        let mut board = record.get_board().clone();
+        let result = Searcher::new(SearchLimits::depth(MATE_SEARCH_DEPTH)).search(&mut board);
+
+        let best_move = result.best_move.expect("The search found no move");
+        assert!(best_moves.contains(&best_move), "Search played {} instead of {:?} in {}", best_move, best_moves, board.to_fen());
+    }
+}
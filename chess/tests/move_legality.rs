@@ -0,0 +1,67 @@
+use chess::{board::Board, config, epd, r#move::{illegal_move_reason::IllegalMoveReason, UciMove}, move_generation::{move_gen, move_list::CrazyhouseMoveList, pseudo_legal_move_gen}, variant::Variant};
+
+/// Depth of the walk checking the single move legality against the move generation
+const LEGALITY_WALK_DEPTH: u8 = 2;
+
+/// Checks that *is_legal* accepts exactly the generated moves among the pseudo legal ones, then does the same
+/// for every position after them
+fn check_legality_walk(board: &mut Board, depth: u8) {
+    let mut legal_moves = CrazyhouseMoveList::new();
+    move_gen::generate_moves(&mut legal_moves, board);
+    let mut candidate_moves = CrazyhouseMoveList::new();
+    pseudo_legal_move_gen::generate_moves(&mut candidate_moves, board);
+
+    for mov in candidate_moves.iter().chain(legal_moves.iter()) {
+        assert_eq!(board.is_legal(mov), legal_moves.contains(mov), "Legality of {} differs from the move generation in {}", mov, board.to_fen());
+    }
+
+    if depth <= 1 {
+        return;
+    }
+
+    for &mov in legal_moves.iter() {
+        let move_record = board.make_move(mov);
+        check_legality_walk(board, depth - 1);
+        board.undo_move(move_record);
+    }
+}
+
+/// The positions of the standard perft suite are walked in every variant too, since they have more tactics
+/// than the starting positions of the variant suites
+fn check_suites_legality(variant: Variant, suite_files: &[&str]) {
+    for suite_file in suite_files {
+        let records = epd::load_epd_file(config::get_test_suite_path(suite_file)).expect("Failed to load the perft suite");
+        assert!(!records.is_empty(), "The perft suite is empty");
+
+        for record in records.iter() {
+            let mut board = record.get_board().clone();
+            board.set_variant(variant);
+            check_legality_walk(&mut board, LEGALITY_WALK_DEPTH);
+        }
+    }
+}
+
+#[test]
+fn single_move_legality_matches_move_generation() {
+    check_suites_legality(Variant::Standard, &[config::PERFT_SUITE_FILE]);
+}
+
+#[test]
+fn single_move_legality_matches_atomic_move_generation() {
+    check_suites_legality(Variant::Atomic, &[config::ATOMIC_PERFT_SUITE_FILE, config::PERFT_SUITE_FILE]);
+}
+
+#[test]
+fn single_move_legality_matches_antichess_move_generation() {
+    check_suites_legality(Variant::Antichess, &[config::ANTICHESS_PERFT_SUITE_FILE, config::PERFT_SUITE_FILE]);
+}
+
+#[test]
+fn pawn_can_not_move_across_the_board() {
+    let mut board = Board::from_fen("k7/8/8/8/8/8/8/K3P3 w - - 0 1");
+    let mov = UciMove::try_from_uci("e1e8").expect("Failed to parse the move");
+
+    let result = board.try_make_move(mov).map(|_| ());
+    assert!(matches!(result, Err(IllegalMoveReason::InvalidPieceMove { .. })), "Expected an invalid piece move, got {:?}", result);
+    assert_eq!(board.to_fen(), "k7/8/8/8/8/8/8/K3P3 w - - 0 1");
+}
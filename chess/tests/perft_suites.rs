@@ -0,0 +1,50 @@
+use chess::{config, epd, perft, variant::Variant};
+
+/// Deepest perft depth run from the suites, the deeper counts take too long for a test run
+const MAX_SUITE_DEPTH: u8 = 5;
+
+#[test]
+fn perft_suite_matches_published_node_counts() {
+    let records = epd::load_epd_file(config::get_test_suite_path(config::PERFT_SUITE_FILE)).expect("Failed to load the perft suite");
+    assert!(!records.is_empty(), "The perft suite is empty");
+
+    let mismatches = perft::run_perft_suite(&records, MAX_SUITE_DEPTH);
+    assert!(mismatches.is_empty(), "Perft mismatches: {:?}", mismatches);
+}
+
+#[test]
+fn atomic_perft_suite_matches_published_node_counts() {
+    let mut records = epd::load_epd_file(config::get_test_suite_path(config::ATOMIC_PERFT_SUITE_FILE)).expect("Failed to load the Atomic perft suite");
+    assert!(!records.is_empty(), "The Atomic perft suite is empty");
+    records.iter_mut().for_each(|record| record.set_variant(Variant::Atomic));
+
+    let mismatches = perft::run_perft_suite(&records, MAX_SUITE_DEPTH);
+    assert!(mismatches.is_empty(), "Atomic perft mismatches: {:?}", mismatches);
+}
+
+#[test]
+fn antichess_perft_suite_matches_published_node_counts() {
+    let mut records = epd::load_epd_file(config::get_test_suite_path(config::ANTICHESS_PERFT_SUITE_FILE)).expect("Failed to load the Antichess perft suite");
+    assert!(!records.is_empty(), "The Antichess perft suite is empty");
+    records.iter_mut().for_each(|record| record.set_variant(Variant::Antichess));
+
+    let mismatches = perft::run_perft_suite(&records, MAX_SUITE_DEPTH);
+    assert!(mismatches.is_empty(), "Antichess perft mismatches: {:?}", mismatches);
+}
+
+/// Depth of the staged perft, every node checks the stages against the full move list so it is much slower
+const STAGED_PERFT_DEPTH: u8 = 4;
+
+#[test]
+fn staged_move_generation_matches_full_move_generation() {
+    let records = epd::load_epd_file(config::get_test_suite_path(config::PERFT_SUITE_FILE)).expect("Failed to load the perft suite");
+    assert!(!records.is_empty(), "The perft suite is empty");
+
+    for record in records.iter() {
+        let mut board = record.get_board().clone();
+        let nodes = perft::run_staged_perft(STAGED_PERFT_DEPTH, &mut board);
+        if let Some((_, expected_nodes)) = record.get_perft_counts().into_iter().find(|&(depth, _)| depth == STAGED_PERFT_DEPTH) {
+            assert_eq!(nodes, expected_nodes, "Staged perft mismatch in {}", board.to_fen());
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use chess::{board::Board, config, epd, move_generation::{move_gen, move_list::CrazyhouseMoveList}, pgn::{pgn_error::PgnError, pgn_game::PgnGame}};
+
+/// Depth of the walk checking the SAN round trip, the suite positions and the replies to them
+const SAN_WALK_DEPTH: u8 = 2;
+
+fn check_san_round_trip(board: &mut Board, depth: u8) {
+    let mut legal_moves = CrazyhouseMoveList::new();
+    move_gen::generate_moves(&mut legal_moves, board);
+
+    for &mov in legal_moves.iter() {
+        let san = mov.to_san(board);
+        assert_eq!(board.parse_san(&san), Ok(mov), "SAN {} doesn't give back {} in {}", san, mov, board.to_fen());
+
+        if depth > 1 {
+            let move_record = board.make_move(mov);
+            check_san_round_trip(board, depth - 1);
+            board.undo_move(move_record);
+        }
+    }
+}
+
+#[test]
+fn san_round_trips_over_perft_suite() {
+    let records = epd::load_epd_file(config::get_test_suite_path(config::PERFT_SUITE_FILE)).expect("Failed to load the perft suite");
+    assert!(!records.is_empty(), "The perft suite is empty");
+
+    for record in records.iter() {
+        let mut board = record.get_board().clone();
+        check_san_round_trip(&mut board, SAN_WALK_DEPTH);
+    }
+}
+
+/// Game with the nested variations, NAGs, the *!?* suffix and comments before and after the moves
+const ANNOTATED_PGN: &str = r#"[Event "Annotated game"]
+[Site "?"]
+[Date "2024.05.01"]
+[Round "1"]
+[White "White"]
+[Black "Black"]
+[Result "1-0"]
+
+{Opening comment} 1. e4 $1 e5 2. Nf3 {Developing} Nc6 (2... d6 {Philidor} 3. d4 (3. Bc4 Be7 (3... Nf6 $2))
+3... Nf6) 3. Bb5!? a6 $6 4. Ba4 Nf6 5. O-O {Castled} 1-0"#;
+
+#[test]
+fn pgn_round_trips_with_variations_nags_and_comments() {
+    let game = PgnGame::from_pgn(ANNOTATED_PGN).expect("Failed to read the annotated game");
+    assert_eq!(game.moves.len(), 9);
+    assert_eq!(game.moves[3].variations[0][1].variations[0][1].variations.len(), 1, "The nested variations were not read");
+
+    let pgn = game.to_pgn().expect("Failed to write the annotated game");
+    assert_eq!(PgnGame::from_pgn(&pgn), Ok(game), "The written game differs:\n{}", pgn);
+}
+
+#[test]
+fn illegal_move_reports_its_ply() {
+    let error = PgnGame::from_pgn("1. e4 e5 2. Nf3 Nc6 3. Bb5 Nf6 4. Bxe5 *").expect_err("The illegal move was accepted");
+    assert!(matches!(&error, PgnError::IllegalMove { ply: 7, san, .. } if san == "Bxe5"), "Unexpected error: {}", error);
+}
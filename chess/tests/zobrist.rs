@@ -0,0 +1,45 @@
+use chess::{board::Board, config, epd, move_generation::{move_gen, move_list::CrazyhouseMoveList}, variant::Variant};
+
+/// Depth of the walk checking the incremental Zobrist key against the one computed from scratch
+const ZOBRIST_WALK_DEPTH: u8 = 3;
+
+fn check_zobrist_walk(board: &mut Board, depth: u8) {
+    let mut legal_moves = CrazyhouseMoveList::new();
+    move_gen::generate_moves(&mut legal_moves, board);
+
+    for &mov in legal_moves.iter() {
+        let fen = board.to_fen();
+        let move_record = board.make_move(mov);
+        assert_eq!(board.get_zobrist_key(), board.compute_hash(), "Zobrist key is out of sync after {} in {}", mov, fen);
+
+        if depth > 1 {
+            check_zobrist_walk(board, depth - 1);
+        }
+
+        board.undo_move(move_record);
+        assert_eq!(board.get_zobrist_key(), board.compute_hash(), "Zobrist key is out of sync after undoing {} in {}", mov, fen);
+    }
+}
+
+fn check_suite_zobrist_keys(suite_file: &str, variant: Variant) {
+    let records = epd::load_epd_file(config::get_test_suite_path(suite_file)).expect("Failed to load the perft suite");
+    assert!(!records.is_empty(), "The perft suite is empty");
+
+    for record in records.iter() {
+        let mut board = record.get_board().clone();
+        board.set_variant(variant);
+        check_zobrist_walk(&mut board, ZOBRIST_WALK_DEPTH);
+    }
+}
+
+#[test]
+fn zobrist_key_stays_in_sync_over_perft_suite() {
+    check_suite_zobrist_keys(config::PERFT_SUITE_FILE, Variant::Standard);
+}
+
+#[test]
+fn zobrist_key_stays_in_sync_in_every_variant() {
+    for variant in [Variant::Crazyhouse, Variant::Atomic, Variant::ThreeCheck, Variant::KingOfTheHill, Variant::Antichess] {
+        check_suite_zobrist_keys(config::PERFT_SUITE_FILE, variant);
+    }
+}
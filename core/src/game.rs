@@ -1,12 +1,158 @@
+use std::fmt::Display;
+
 pub enum GameMode {
     PvP,
     PvB,
     BvB
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GameState {
     InProgress,
-    WhiteWon,
-    BlackWon,
-    Draw
-}
\ No newline at end of file
+    WhiteWon(Termination),
+    BlackWon(Termination),
+    Draw(Termination)
+}
+
+/// Describes why the game has ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Termination {
+    Checkmate,
+    Stalemate,
+    Resignation,
+    /// One of the players has run out of time
+    Timeout,
+    /// The same position has occurred three times, the draw can be claimed
+    ThreefoldRepetition,
+    /// The same position has occurred five times, the game is drawn automatically
+    FivefoldRepetition,
+    /// No captures or pawn moves were made in the last 50 moves, the draw can be claimed
+    FiftyMoveRule,
+    /// No captures or pawn moves were made in the last 75 moves, the game is drawn automatically
+    SeventyFiveMoveRule,
+    /// Neither side can checkmate the other by any sequence of moves
+    InsufficientMaterial,
+    /// The players have agreed to a draw
+    Agreement,
+    /// The result was decided by an arbiter or a tournament manager
+    Adjudication,
+    /// One of the players has left the game
+    Abandonment,
+    /// One of the players has broken the rules and lost the game
+    RulesInfraction,
+    /// The king was blown up by a capture next to it in Atomic chess
+    KingExploded,
+    /// One of the players has given the third check in Three-check
+    ThirdCheck,
+    /// The king has reached one of the four central squares in King of the Hill
+    KingOfTheHill,
+    /// One of the players has lost all the pieces in Antichess and won the game
+    AllPiecesLost,
+    /// The game ended by the regular rules but the exact reason is unknown,
+    /// like in games loaded from PGN with the *normal* termination
+    Normal,
+}
+
+impl GameState {
+    pub fn is_finished(&self) -> bool {
+        *self != GameState::InProgress
+    }
+
+    pub fn get_termination(&self) -> Option<Termination> {
+        match self {
+            GameState::InProgress => None,
+            GameState::WhiteWon(termination)
+            | GameState::BlackWon(termination)
+            | GameState::Draw(termination) => Some(*termination),
+        }
+    }
+
+    /// Returns the value of the PGN *Result* tag
+    pub fn get_pgn_result(&self) -> &'static str {
+        match self {
+            GameState::InProgress => "*",
+            GameState::WhiteWon(_) => "1-0",
+            GameState::BlackWon(_) => "0-1",
+            GameState::Draw(_) => "1/2-1/2",
+        }
+    }
+
+    /// Returns the value of the PGN *Termination* tag
+    pub fn get_pgn_termination(&self) -> &'static str {
+        match self.get_termination() {
+            Some(termination) => termination.get_pgn_termination(),
+            None => "unterminated",
+        }
+    }
+
+    /// Creates the game state from the PGN *Result* and *Termination* tags
+    ///
+    /// Returns *None* if the result is not one of *1-0*, *0-1*, *1/2-1/2*, *\**
+    pub fn from_pgn_tags(result: &str, termination: Option<&str>) -> Option<GameState> {
+        let termination = termination
+            .and_then(Termination::from_pgn_termination)
+            .unwrap_or(Termination::Normal);
+
+        match result.trim() {
+            "*" => Some(GameState::InProgress),
+            "1-0" => Some(GameState::WhiteWon(termination)),
+            "0-1" => Some(GameState::BlackWon(termination)),
+            "1/2-1/2" => Some(GameState::Draw(termination)),
+            _ => None,
+        }
+    }
+}
+
+impl Termination {
+    /// Returns the value of the PGN *Termination* tag
+    ///
+    /// The PGN standard doesn't distinguish between the regular endings
+    /// like checkmate or repetition, all of them are *normal*
+    pub fn get_pgn_termination(&self) -> &'static str {
+        match self {
+            Termination::Timeout => "time forfeit",
+            Termination::Adjudication => "adjudication",
+            Termination::Abandonment => "abandoned",
+            Termination::RulesInfraction => "rules infraction",
+            _ => "normal",
+        }
+    }
+
+    /// Parses the value of the PGN *Termination* tag (case insensitive)
+    pub fn from_pgn_termination(termination: &str) -> Option<Termination> {
+        match termination.trim().to_ascii_lowercase().as_str() {
+            "normal" => Some(Termination::Normal),
+            "time forfeit" => Some(Termination::Timeout),
+            "adjudication" => Some(Termination::Adjudication),
+            "abandoned" => Some(Termination::Abandonment),
+            "rules infraction" => Some(Termination::RulesInfraction),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Termination {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let description = match self {
+            Termination::Checkmate => "checkmate",
+            Termination::Stalemate => "stalemate",
+            Termination::Resignation => "resignation",
+            Termination::Timeout => "timeout",
+            Termination::ThreefoldRepetition => "threefold repetition",
+            Termination::FivefoldRepetition => "fivefold repetition",
+            Termination::FiftyMoveRule => "fifty-move rule",
+            Termination::SeventyFiveMoveRule => "seventy-five-move rule",
+            Termination::InsufficientMaterial => "insufficient material",
+            Termination::Agreement => "agreement",
+            Termination::Adjudication => "adjudication",
+            Termination::Abandonment => "abandonment",
+            Termination::RulesInfraction => "rules infraction",
+            Termination::KingExploded => "king explosion",
+            Termination::ThirdCheck => "third check",
+            Termination::KingOfTheHill => "reaching the hill",
+            Termination::AllPiecesLost => "losing all pieces",
+            Termination::Normal => "normal termination",
+        };
+        write!(f, "{}", description)
+    }
+}
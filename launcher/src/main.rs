@@ -1,4 +1,4 @@
-use chess::{board::Board, board_representation, config, move_generation::{attack_calculator::AttackCalculator, move_gen}, perft};
+use chess::{board::Board, board_representation, config, move_generation::{attack_calculator::AttackCalculator, move_gen, move_list::MoveList}, perft};
 
 #[global_allocator]
 static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
@@ -12,8 +12,8 @@ async fn main() -> std::io::Result<()> {
 
     let mut board = Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq -");
     let attack_calc = AttackCalculator::new(&board);
-    let mut legal_moves = Vec::with_capacity(218);
-    move_gen::generate_moves(&mut legal_moves, &mut board);
+    let mut legal_moves: MoveList = MoveList::new();
+    move_gen::generate_moves(&mut legal_moves, &board);
     board_representation::print_board(&board);
     AttackCalculator::print(&attack_calc);
     move_gen::print(&legal_moves);
@@ -88,6 +88,13 @@ pub async fn set_position(fen: &str) -> std::io::Result<()> {
     Ok(())
 }
 
+/// Switches the castling moves between the regular notation and the king-takes-rook one of Chess960
+pub async fn set_chess960(is_chess960: bool) -> std::io::Result<()> {
+    let command = format!("setoption name UCI_Chess960 value {}", is_chess960);
+    send_command(&command).await?;
+    Ok(())
+}
+
 async fn send_command(command: &str) -> std::io::Result<()> {
     let service_mutex = STOCKFISH_SERVICE.lock().await;
     if let Some(service) = service_mutex.as_ref() {
@@ -25,7 +25,7 @@ impl StockfishPerftResponse {
                     let uci_mov = UciMove::from_uci(parts[0]);
                     if let UciMoveCreationResult::Success(mov) = uci_mov {
                         if let Ok(node_count) = parts[1].parse::<u64>() {
-                            nodes.push(PerftNode::new(mov, node_count as usize));
+                            nodes.push(PerftNode::new(mov.into(), node_count as usize));
                         }
                     }
                 }